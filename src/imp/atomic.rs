@@ -1,10 +1,10 @@
 use core::{
-    fmt,
-    marker::PhantomData,
-    sync::atomic::{AtomicUsize, Ordering},
+    fmt, hint, iter, mem,
+    ptr::{self, NonNull},
+    sync::atomic::Ordering,
 };
 
-use crate::{AtomicTagPtr, TagPtr};
+use crate::{fence, AtomicPtr, AtomicTagPtr, MisalignedError, Null, SeqGuard, TagNonNull, TagPtr};
 
 /********** impl Send + Sync **********************************************************************/
 
@@ -45,16 +45,50 @@ impl<T, const N: usize> AtomicTagPtr<T, N> {
         ///     (ptr::null_mut(), 0)
         /// );
         /// ```
+        #[cfg(not(loom))]
         pub const fn null() -> Self {
-            Self { inner: AtomicUsize::new(0), _marker: PhantomData }
+            Self { inner: AtomicPtr::new(ptr::null_mut()) }
+        }
+    }
+
+    doc_comment! {
+        doc_null!(),
+        #[cfg(loom)]
+        pub fn null() -> Self {
+            Self { inner: AtomicPtr::new(ptr::null_mut()) }
+        }
+    }
+
+    doc_comment! {
+        doc_atomic_new!(),
+        ///
+        /// Like [`null`][AtomicTagPtr::null], this can be used for
+        /// initializing `static` items.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use core::sync::atomic::Ordering;
+        ///
+        /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+        /// type TagPtr = tagptr::TagPtr<i32, 2>;
+        ///
+        /// static HEAD: AtomicTagPtr = AtomicTagPtr::new(TagPtr::null());
+        /// assert!(HEAD.load(Ordering::Relaxed).is_null());
+        /// ```
+        #[cfg(not(loom))]
+        #[inline]
+        pub const fn new(marked_ptr: TagPtr<T, N>) -> Self {
+            Self { inner: AtomicPtr::new(marked_ptr.into_raw()) }
         }
     }
 
     doc_comment! {
         doc_atomic_new!(),
+        #[cfg(loom)]
         #[inline]
         pub fn new(marked_ptr: TagPtr<T, N>) -> Self {
-            Self { inner: AtomicUsize::new(marked_ptr.into_usize()), _marker: PhantomData }
+            Self { inner: AtomicPtr::new(marked_ptr.into_raw()) }
         }
     }
 
@@ -62,18 +96,114 @@ impl<T, const N: usize> AtomicTagPtr<T, N> {
         doc_atomic_into_inner!(),
         #[inline]
         pub fn into_inner(self) -> TagPtr<T, N> {
-            TagPtr::from_usize(self.inner.into_inner())
+            TagPtr::new(self.inner.into_inner())
         }
     }
 
+    /// Creates a new atomic marked pointer from an [`AtomicPtr`], interpreting
+    /// its current value *as is*, i.e., including any potential tag bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::{AtomicPtr, Ordering};
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::from_atomic_ptr(AtomicPtr::new(reference));
+    /// assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0));
+    /// ```
+    #[inline]
+    pub const fn from_atomic_ptr(ptr: AtomicPtr<T>) -> Self {
+        Self { inner: ptr }
+    }
+
+    /// Consumes the atomic marked pointer and returns the underlying
+    /// [`AtomicPtr`].
+    ///
+    /// The tag bits are retained in the returned pointer's value, which hence
+    /// appears to point at a (potentially misaligned) tagged address and
+    /// must not be dereferenced without stripping the tag first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b10)).into_atomic_ptr();
+    /// assert_eq!(ptr.load(Ordering::Relaxed), TagPtr::compose(reference, 0b10).into_raw());
+    /// ```
+    #[inline]
+    pub fn into_atomic_ptr(self) -> AtomicPtr<T> {
+        self.inner
+    }
+
     /// Returns a mutable reference to the underlying marked pointer.
     ///
     /// This is safe because the mutable reference guarantees no other
     /// threads are concurrently accessing the atomic pointer.
+    #[cfg(not(loom))]
     #[inline]
     pub fn get_mut(&mut self) -> &mut TagPtr<T, N> {
         // SAFETY: the mutable self reference ensures the dereferencing is sound
-        unsafe { &mut *(self.inner.get_mut() as *mut usize as *mut _) }
+        unsafe { &mut *(self.inner.get_mut() as *mut *mut T as *mut _) }
+    }
+
+    /// Loads the value of the atomic marked pointer non-atomically.
+    ///
+    /// This is safe because the mutable reference guarantees no other
+    /// threads are concurrently accessing the atomic pointer, so no memory
+    /// ordering is required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let mut ptr = AtomicTagPtr::new(TagPtr::compose(&mut 1, 0b01));
+    /// assert_eq!(ptr.load_mut().decompose_tag(), 0b01);
+    /// ```
+    #[inline]
+    pub fn load_mut(&mut self) -> TagPtr<T, N> {
+        #[cfg(not(loom))]
+        let ptr = *self.inner.get_mut();
+        #[cfg(loom)]
+        let ptr = self.inner.with_mut(|ptr| *ptr);
+        TagPtr::new(ptr)
+    }
+
+    /// Stores `marked_ptr` into the atomic marked pointer non-atomically.
+    ///
+    /// This is safe because the mutable reference guarantees no other
+    /// threads are concurrently accessing the atomic pointer, so no memory
+    /// ordering is required.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let mut ptr = AtomicTagPtr::null();
+    /// ptr.store_mut(TagPtr::compose(&mut 1, 0b10));
+    /// assert_eq!(ptr.load(Ordering::Relaxed).decompose_tag(), 0b10);
+    /// ```
+    #[inline]
+    pub fn store_mut(&mut self, marked_ptr: TagPtr<T, N>) {
+        #[cfg(not(loom))]
+        {
+            *self.inner.get_mut() = marked_ptr.into_raw();
+        }
+        #[cfg(loom)]
+        self.inner.with_mut(|ptr| *ptr = marked_ptr.into_raw());
     }
 
     /// Loads the value of the atomic marked pointer.
@@ -94,43 +224,41 @@ impl<T, const N: usize> AtomicTagPtr<T, N> {
     /// [seq_cst]: Ordering::SeqCst
     #[inline]
     pub fn load(&self, order: Ordering) -> TagPtr<T, N> {
-        TagPtr::from_usize(self.inner.load(order))
+        crate::debug_assert_load_ordering(order);
+        TagPtr::new(self.inner.load(order))
     }
 
-    /// Stores a value into the atomic marked pointer.
+    /// Loads the value of the atomic marked pointer, returning only the
+    /// separated raw pointer.
     ///
-    /// `store` takes an [`Ordering`] argument which describes the memory
-    /// ordering of this operation.
-    /// Possible values are [`SeqCst`][seq_cst], [`Release`][rel] and
-    /// [`Relaxed`][rlx].
+    /// `load_ptr` takes an [`Ordering`] argument which describes the memory
+    /// ordering of this operation, with the same restrictions as for
+    /// [`load`][AtomicTagPtr::load].
     ///
-    /// # Panics
+    /// # Examples
     ///
-    /// Panics if `order` is [`Acquire`][acq] or [`AcqRel`][acq_rel].
+    /// ```
+    /// use core::sync::atomic::Ordering;
     ///
-    /// [rlx]: Ordering::Relaxed
-    /// [acq]: Ordering::Acquire
-    /// [rel]: Ordering::Release
-    /// [acq_rel]: Ordering::AcqRel
-    /// [seq_cst]: Ordering::SeqCst
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b10));
+    /// assert_eq!(ptr.load_ptr(Ordering::Relaxed), reference as *mut _);
+    /// assert_eq!(ptr.load_ptr(Ordering::Relaxed), ptr.load(Ordering::Relaxed).decompose_ptr());
+    /// ```
     #[inline]
-    pub fn store(&self, ptr: TagPtr<T, N>, order: Ordering) {
-        self.inner.store(ptr.into_usize(), order)
+    pub fn load_ptr(&self, order: Ordering) -> *mut T {
+        self.load(order).decompose_ptr()
     }
 
-    /// Stores a value into the atomic marked pointer and returns the previous
-    /// value.
-    ///
-    /// `swap` takes an [`Ordering`] argument which describes the memory
-    /// ordering of this operation.
-    /// All ordering modes are possible.
-    /// Note that using [`Acquire`][acq] makes the store part of this operation
-    /// [`Relaxed`][rlx], and using [`Release`][rel] makes the load part
-    /// [`Relaxed`][rlx].
+    /// Loads the value of the atomic marked pointer, returning only the
+    /// separated tag value.
     ///
-    /// [rlx]: Ordering::Relaxed
-    /// [acq]: Ordering::Acquire
-    /// [rel]: Ordering::Release
+    /// `load_tag` takes an [`Ordering`] argument which describes the memory
+    /// ordering of this operation, with the same restrictions as for
+    /// [`load`][AtomicTagPtr::load].
     ///
     /// # Examples
     ///
@@ -140,111 +268,87 @@ impl<T, const N: usize> AtomicTagPtr<T, N> {
     /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
     /// type TagPtr = tagptr::TagPtr<i32, 2>;
     ///
-    /// let ptr = AtomicTagPtr::null();
-    /// let prev = ptr.swap(TagPtr::new(&mut 1), Ordering::Relaxed);
-    ///
-    /// assert!(prev.is_null());
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(&mut 1, 0b10));
+    /// assert_eq!(ptr.load_tag(Ordering::Relaxed), 0b10);
+    /// assert_eq!(ptr.load_tag(Ordering::Relaxed), ptr.load(Ordering::Relaxed).decompose_tag());
     /// ```
-    pub fn swap(&self, ptr: TagPtr<T, N>, order: Ordering) -> TagPtr<T, N> {
-        TagPtr::from_usize(self.inner.swap(ptr.into_usize(), order))
+    #[inline]
+    pub fn load_tag(&self, order: Ordering) -> usize {
+        self.load(order).decompose_tag()
     }
 
-    /// Stores a value into the pointer if the current value is the same as
-    /// `current`.
+    /// Loads the value of the atomic marked pointer and attempts to convert it
+    /// into a non-nullable marked pointer.
     ///
-    /// The return value is a result indicating whether the new value was
-    /// written and containing the previous value.
-    /// On success this value is guaranteed to be equal to `current`.
+    /// `load_non_null` takes an [`Ordering`] argument which describes the
+    /// memory ordering of this operation, with the same restrictions as for
+    /// [`load`][AtomicTagPtr::load].
     ///
-    /// `compare_exchange` takes takes two [`Ordering`] arguments to describe
-    /// the memory ordering of this operation.
-    /// The first describes the required ordering if the operation succeeds
-    /// while the second describes the required ordering when the operation
-    /// fails.
-    /// Using [`Acquire`][acq] as success ordering makes store part of this
-    /// operation [`Relaxed`][rlx], and using [`Release`][rel] makes the
-    /// successful load [`Relaxed`][rlx].
-    /// The failure ordering can only be [`SeqCst`][seq_cst], [`Acquire`][acq]
-    /// or [`Relaxed`][rlx] and must be equivalent or weaker than the success
-    /// ordering.
+    /// # Errors
     ///
-    /// [rlx]: Ordering::Relaxed
-    /// [acq]: Ordering::Acquire
-    /// [rel]: Ordering::Release
-    /// [seq_cst]: Ordering::SeqCst
-    #[inline]
-    pub fn compare_exchange(
-        &self,
-        current: TagPtr<T, N>,
-        new: TagPtr<T, N>,
-        (success, failure): (Ordering, Ordering),
-    ) -> Result<TagPtr<T, N>, TagPtr<T, N>> {
-        self.inner
-            .compare_exchange(current.into_usize(), new.into_usize(), success, failure)
-            .map(|_| current)
-            .map_err(TagPtr::from_usize)
-    }
-
-    /// Stores a value into the pointer if the current value is the same as
-    /// `current`.
+    /// Fails if the loaded pointer is `null`, in which case a [`Null`]
+    /// instance is returned containing the loaded pointer's tag value.
     ///
-    /// The return value is a result indicating whether the new value was
-    /// written and containing the previous value.
-    /// On success this value is guaranteed to be equal to `current`.
+    /// # Examples
     ///
-    /// Unlike `compare_exchange`, this function is allowed to spuriously fail,
-    /// even when the comparison succeeds, which can result in more efficient
-    /// code on some platforms.
-    /// The return value is a result indicating whether the new value was
-    /// written and containing the previous value.
+    /// ```
+    /// use core::{ptr::{self, NonNull}, sync::atomic::Ordering};
     ///
-    /// `compare_exchange` takes takes two [`Ordering`] arguments to describe
-    /// the memory ordering of this operation.
-    /// The first describes the required ordering if the operation succeeds
-    /// while the second describes the required ordering when the operation
-    /// fails.
-    /// Using [`Acquire`][acq] as success ordering makes store part of this
-    /// operation [`Relaxed`][rlx], and using [`Release`][rel] makes the
-    /// successful load [`Relaxed`][rlx].
-    /// The failure ordering can only be [`SeqCst`][seq_cst], [`Acquire`][acq]
-    /// or [`Relaxed`][rlx] and must be equivalent or weaker than the success
+    /// use tagptr::Null;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(ptr::null_mut(), 0b01));
+    /// assert_eq!(ptr.load_non_null(Ordering::Relaxed), Err(Null(0b01)));
+    ///
+    /// let reference = &mut 1;
+    /// ptr.store(TagPtr::compose(reference, 0b10), Ordering::Relaxed);
+    /// let res = ptr.load_non_null(Ordering::Relaxed).map(|ptr| ptr.decompose());
+    /// assert_eq!(res, Ok((NonNull::from(reference), 0b10)));
+    /// ```
+    #[inline]
+    pub fn load_non_null(&self, order: Ordering) -> Result<TagNonNull<T, N>, Null> {
+        TagNonNull::new(self.load(order))
+    }
+
+    /// Loads the value of the atomic marked pointer with [`Acquire`][acq]
     /// ordering.
     ///
-    /// [rlx]: Ordering::Relaxed
+    /// This is equivalent to [`load`][AtomicTagPtr::load] with
+    /// [`Acquire`][acq] ordering but explicitly denotes the pairing with a
+    /// [`Release`][rel] store (publish) of the same value, e.g., for reading
+    /// a sequence counter in the tag bits.
+    /// See [`SeqGuard`] for an application.
+    ///
     /// [acq]: Ordering::Acquire
     /// [rel]: Ordering::Release
-    /// [seq_cst]: Ordering::SeqCst
     #[inline]
-    pub fn compare_exchange_weak(
-        &self,
-        current: TagPtr<T, N>,
-        new: TagPtr<T, N>,
-        (success, failure): (Ordering, Ordering),
-    ) -> Result<TagPtr<T, N>, TagPtr<T, N>> {
-        self.inner
-            .compare_exchange_weak(current.into_usize(), new.into_usize(), success, failure)
-            .map(|_| current)
-            .map_err(TagPtr::from_usize)
+    pub fn read_acquire_consistent(&self) -> TagPtr<T, N> {
+        self.load(Ordering::Acquire)
     }
 
-    /// Adds `value` to the current tag value, returning the previous marked
-    /// pointer.
+    /// Begins an optimistic read section by taking a snapshot of the current
+    /// value, interpreting the tag value as sequence counter.
     ///
-    /// This operation directly and unconditionally alters the internal numeric
-    /// representation of the atomic marked pointer.
-    /// Hence there is no way to reliably guarantee the operation only affects
-    /// the tag bits and does not overflow into the pointer bits.
+    /// See [`SeqGuard`] for details.
+    #[inline]
+    pub fn seq_guard(&self) -> SeqGuard<'_, T, N> {
+        SeqGuard { atomic: self, snapshot: self.read_acquire_consistent() }
+    }
+
+    /// Repeatedly loads the value until `predicate` returns `true` for it or
+    /// `max_spins` retries have been exhausted.
     ///
-    /// `fetch_add` takes takes an [`Ordering`] argument which describes the
-    /// memory ordering of this operation.
-    /// All ordering modes are possible.
-    /// Note that using [`Acquire`][acq] makes the store part of this operation
-    /// [`Relaxed`][rlx] and using [`Release`][rel] makes the load part
-    /// [`Relaxed`][rlx].
+    /// Returns the first loaded value matching `predicate` or `None`, if no
+    /// such value was observed.
+    /// Each retry is preceded by a [`spin_loop`][hint::spin_loop] hint, so
+    /// this can be used for bounded (busy) waiting, e.g., for another thread
+    /// to publish a pointer.
+    /// With `max_spins` equal to 0, the value is loaded exactly once.
     ///
-    /// [rlx]: Ordering::Relaxed
-    /// [acq]: Ordering::Acquire
-    /// [rel]: Ordering::Release
+    /// `load_until` takes an [`Ordering`] argument with the same restrictions
+    /// as for [`load`][AtomicTagPtr::load], which is used for every load.
     ///
     /// # Examples
     ///
@@ -257,51 +361,1014 @@ impl<T, const N: usize> AtomicTagPtr<T, N> {
     /// let reference = &mut 1;
     /// let ptr = AtomicTagPtr::new(TagPtr::new(reference));
     ///
-    /// assert_eq!(
-    ///     ptr.fetch_add(1, Ordering::Relaxed).decompose(),
-    ///     (reference as *mut _, 0)
-    /// );
-    ///
-    /// assert_eq!(
-    ///     ptr.load(Ordering::Relaxed).decompose(),
-    ///     (reference as *mut _, 0b01)
-    /// );
+    /// let res = ptr.load_until(|ptr| !ptr.is_null(), 100, Ordering::Acquire);
+    /// assert_eq!(res, Some(TagPtr::new(reference)));
+    /// let res = ptr.load_until(|ptr| ptr.decompose_tag() == 0b1, 100, Ordering::Acquire);
+    /// assert_eq!(res, None);
     /// ```
     #[inline]
-    pub fn fetch_add(&self, value: usize, order: Ordering) -> TagPtr<T, N> {
-        debug_assert!(value < Self::TAG_MASK, "`value` exceeds tag bits (would overflow)");
-        TagPtr::from_usize(self.inner.fetch_add(value, order))
+    pub fn load_until(
+        &self,
+        predicate: impl Fn(TagPtr<T, N>) -> bool,
+        max_spins: u32,
+        order: Ordering,
+    ) -> Option<TagPtr<T, N>> {
+        let mut spins = 0;
+        loop {
+            let curr = self.load(order);
+            if predicate(curr) {
+                return Some(curr);
+            }
+
+            if spins == max_spins {
+                return None;
+            }
+
+            spins += 1;
+            hint::spin_loop();
+        }
     }
 
-    /// Subtracts `value` from the current tag value, returning the previous
-    /// marked pointer.
-    ///
-    /// This operation directly and unconditionally alters the internal numeric
-    /// representation of the atomic marked pointer.
-    /// Hence there is no way to reliably guarantee the operation only affects
-    /// the tag bits and does not overflow into the pointer bits.
+    /// Stores a value into the atomic marked pointer.
     ///
-    /// `fetch_sub` takes takes an [`Ordering`] argument which describes the
-    /// memory ordering of this operation.
-    /// All ordering modes are possible.
-    /// Note that using [`Acquire`][acq] makes the store part of this operation
-    /// [`Relaxed`][rlx] and using [`Release`][rel] makes the load part
+    /// `store` takes an [`Ordering`] argument which describes the memory
+    /// ordering of this operation.
+    /// Possible values are [`SeqCst`][seq_cst], [`Release`][rel] and
     /// [`Relaxed`][rlx].
     ///
+    /// # Panics
+    ///
+    /// Panics if `order` is [`Acquire`][acq] or [`AcqRel`][acq_rel].
+    ///
     /// [rlx]: Ordering::Relaxed
     /// [acq]: Ordering::Acquire
     /// [rel]: Ordering::Release
+    /// [acq_rel]: Ordering::AcqRel
+    /// [seq_cst]: Ordering::SeqCst
+    #[inline]
+    pub fn store(&self, ptr: TagPtr<T, N>, order: Ordering) {
+        crate::debug_assert_store_ordering(order);
+        self.inner.store(ptr.into_raw(), order)
+    }
+
+    /// Stores a value into the atomic marked pointer, if its pointer bits are
+    /// well-aligned for `T`.
+    ///
+    /// Returns an error containing the misaligned address without storing
+    /// anything otherwise, which catches computed pointers that have lost
+    /// their alignment before being published.
+    /// Note that any misalignment *within* the tag bits can not be detected,
+    /// since it is indistinguishable from a tag value.
+    ///
+    /// `checked_store` takes an [`Ordering`] argument with the same
+    /// restrictions as for [`store`][AtomicTagPtr::store].
     ///
     /// # Examples
     ///
     /// ```
-    /// use core::sync::atomic::Ordering;
+    /// use core::{ptr, sync::atomic::Ordering};
     ///
-    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
-    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    /// use tagptr::MisalignedError;
     ///
-    /// let reference = &mut 1;
-    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b10));
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<u64, 2>;
+    /// type TagPtr = tagptr::TagPtr<u64, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::null();
+    ///
+    /// let res = ptr.checked_store(TagPtr::compose(reference, 0b01), Ordering::Relaxed);
+    /// assert_eq!(res, Ok(()));
+    ///
+    /// let misaligned = ptr::without_provenance_mut(0x1004);
+    /// let res = ptr.checked_store(TagPtr::compose(misaligned, 0b10), Ordering::Relaxed);
+    /// assert_eq!(res, Err(MisalignedError(0x1004)));
+    /// assert_eq!(ptr.load(Ordering::Relaxed), TagPtr::compose(reference, 0b01));
+    /// ```
+    #[inline]
+    pub fn checked_store(&self, ptr: TagPtr<T, N>, order: Ordering) -> Result<(), MisalignedError> {
+        // the tag bits are always cleared, so only alignment bits above them
+        // can be set
+        let addr = ptr.decompose_ptr().addr();
+        if addr & (mem::align_of::<T>() - 1) != 0 {
+            return Err(MisalignedError(addr));
+        }
+
+        self.store(ptr, order);
+        Ok(())
+    }
+
+    /// Stores a value into the atomic marked pointer and returns the previous
+    /// value.
+    ///
+    /// `swap` takes an [`Ordering`] argument which describes the memory
+    /// ordering of this operation.
+    /// All ordering modes are possible.
+    /// Note that using [`Acquire`][acq] makes the store part of this operation
+    /// [`Relaxed`][rlx], and using [`Release`][rel] makes the load part
+    /// [`Relaxed`][rlx].
+    ///
+    /// [rlx]: Ordering::Relaxed
+    /// [acq]: Ordering::Acquire
+    /// [rel]: Ordering::Release
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let ptr = AtomicTagPtr::null();
+    /// let prev = ptr.swap(TagPtr::new(&mut 1), Ordering::Relaxed);
+    ///
+    /// assert!(prev.is_null());
+    /// ```
+    #[inline]
+    pub fn swap(&self, ptr: TagPtr<T, N>, order: Ordering) -> TagPtr<T, N> {
+        TagPtr::new(self.inner.swap(ptr.into_raw(), order))
+    }
+
+    /// Stores a value into the atomic marked pointer, returning the previous
+    /// value with its tag cleared.
+    ///
+    /// This is equivalent to calling [`clear_tag`][TagPtr::clear_tag] on the
+    /// result of [`swap`][AtomicTagPtr::swap], e.g., for dereferencing the
+    /// previous head of a lock-free stack after replacing it.
+    ///
+    /// `swap_and_clear` takes an [`Ordering`] argument which describes the
+    /// memory ordering of this operation, with the same restrictions as for
+    /// [`swap`][AtomicTagPtr::swap].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b11));
+    /// let prev = ptr.swap_and_clear(TagPtr::null(), Ordering::Relaxed);
+    ///
+    /// assert_eq!(prev.decompose(), (reference as *mut _, 0));
+    /// ```
+    #[inline]
+    pub fn swap_and_clear(&self, ptr: TagPtr<T, N>, order: Ordering) -> TagPtr<T, N> {
+        self.swap(ptr, order).clear_tag()
+    }
+
+    /// Composes `ptr` with the tag value `extra_tag` and stores the result
+    /// into the atomic marked pointer, returning the previous value.
+    ///
+    /// This allows publishing and marking a pointer in a single operation.
+    /// The same caveats as for [`TagPtr::compose`] apply.
+    ///
+    /// `swap_ptr_or_tag` takes an [`Ordering`] argument which describes the
+    /// memory ordering of this operation, with the same restrictions as for
+    /// [`swap`][AtomicTagPtr::swap].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let (a, b) = (&mut 1, &mut 2);
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(a, 0b01));
+    ///
+    /// let prev = ptr.swap_ptr_or_tag(b, 0b10, Ordering::AcqRel);
+    /// assert_eq!(prev.decompose(), (a as *mut _, 0b01));
+    /// assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (b as *mut _, 0b10));
+    /// ```
+    #[inline]
+    pub fn swap_ptr_or_tag(&self, ptr: *mut T, extra_tag: usize, order: Ordering) -> TagPtr<T, N> {
+        self.swap(TagPtr::compose(ptr, extra_tag), order)
+    }
+
+    /// Stores a `null` pointer with a zero tag into the atomic marked pointer
+    /// and returns the previous value in its decomposed form.
+    ///
+    /// `take_decompose` takes an [`Ordering`] argument with the same meaning
+    /// as for [`swap`][AtomicTagPtr::swap].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b10));
+    ///
+    /// assert_eq!(ptr.take_decompose(Ordering::Relaxed), (reference as *mut _, 0b10));
+    /// assert_eq!(ptr.load(Ordering::Relaxed).into_usize(), 0);
+    /// ```
+    #[inline]
+    pub fn take_decompose(&self, order: Ordering) -> (*mut T, usize) {
+        self.swap(TagPtr::null(), order).decompose()
+    }
+
+    /// Detaches the entire (linked) list starting at the current pointer by
+    /// replacing it with `null` and returns an iterator over all of its nodes.
+    ///
+    /// The function `next_of` is called for each node to determine the next
+    /// node in the list, which ends with the first `null` pointer.
+    /// Tag values are ignored.
+    /// Since `next_of` is called for each node *before* it is yielded by the
+    /// iterator, it is possible to reclaim the yielded nodes while iterating.
+    ///
+    /// `drain_list` takes an [`Ordering`] argument which describes the memory
+    /// ordering of the swap operation used for detaching the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::{ptr::NonNull, sync::atomic::Ordering};
+    ///
+    /// use tagptr::{AtomicTagPtr, TagPtr};
+    ///
+    /// struct Node {
+    ///     next: TagPtr<Node, 2>,
+    ///     value: i32,
+    /// }
+    ///
+    /// let mut tail = Node { next: TagPtr::null(), value: 2 };
+    /// let mut head = Node { next: TagPtr::compose(&mut tail, 0b1), value: 1 };
+    ///
+    /// let list = AtomicTagPtr::new(TagPtr::new(&mut head));
+    /// let next_of = |node: NonNull<Node>| unsafe { node.as_ref().next };
+    /// let mut iter = list.drain_list(next_of, Ordering::Acquire).map(|node| unsafe {
+    ///     node.as_ref().value
+    /// });
+    ///
+    /// assert_eq!((iter.next(), iter.next(), iter.next()), (Some(1), Some(2), None));
+    /// assert!(list.load(Ordering::Relaxed).is_null());
+    /// ```
+    #[inline]
+    pub fn drain_list(
+        &self,
+        next_of: impl Fn(NonNull<T>) -> TagPtr<T, N>,
+        order: Ordering,
+    ) -> impl Iterator<Item = NonNull<T>> {
+        let head = self.swap(TagPtr::null(), order);
+        iter::successors(NonNull::new(head.decompose_ptr()), move |&node| {
+            NonNull::new(next_of(node).decompose_ptr())
+        })
+    }
+
+    /// Pops the first node off the (versioned) stack starting at the current
+    /// pointer and returns it along with the stack's new version.
+    ///
+    /// The tag value is used as version counter, which is (wrappingly)
+    /// incremented with every successful pop, so that an intermediate
+    /// pop-and-push of the same node (ABA) is detected by the final
+    /// compare-exchange.
+    /// The function `next_of` is called with the current head node to
+    /// determine its successor, the tag value it returns is ignored.
+    /// It may be called multiple times, if the stack is concurrently modified.
+    /// Returns `None` if the stack is empty.
+    ///
+    /// The current head is loaded with [`Acquire`][acq] ordering and the final
+    /// compare-exchange uses [`AcqRel`][acq_rel] ordering on success.
+    /// Note, that the (versioned) tag only makes ABA *unlikely* (depending on
+    /// the number of tag bits) and does nothing to prevent `next_of` from
+    /// accessing a node that has been concurrently popped and freed by another
+    /// thread.
+    ///
+    /// [acq]: Ordering::Acquire
+    /// [acq_rel]: Ordering::AcqRel
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::{ptr::NonNull, sync::atomic::Ordering};
+    ///
+    /// use tagptr::{AtomicTagPtr, TagPtr};
+    ///
+    /// struct Node {
+    ///     next: TagPtr<Node, 2>,
+    ///     value: i32,
+    /// }
+    ///
+    /// let mut tail = Node { next: TagPtr::null(), value: 2 };
+    /// let mut head = Node { next: TagPtr::new(&mut tail), value: 1 };
+    ///
+    /// let stack = AtomicTagPtr::new(TagPtr::compose(&mut head, 0b11));
+    /// let next_of = |node: NonNull<Node>| unsafe { node.as_ref().next };
+    ///
+    /// let (node, version) = stack.pop_versioned(next_of).unwrap();
+    /// assert_eq!((unsafe { node.as_ref().value }, version), (1, 0b00));
+    /// let (node, version) = stack.pop_versioned(next_of).unwrap();
+    /// assert_eq!((unsafe { node.as_ref().value }, version), (2, 0b01));
+    /// assert!(stack.pop_versioned(next_of).is_none());
+    /// ```
+    #[inline]
+    pub fn pop_versioned(
+        &self,
+        next_of: impl Fn(NonNull<T>) -> TagPtr<T, N>,
+    ) -> Option<(NonNull<T>, usize)> {
+        let mut curr = self.load(Ordering::Acquire);
+        loop {
+            let (ptr, version) = curr.decompose();
+            let node = NonNull::new(ptr)?;
+            let next = next_of(node).decompose_ptr();
+            let version = version.wrapping_add(1) & Self::TAG_MASK;
+
+            let new = TagPtr::compose(next, version);
+            match self.compare_exchange_weak(curr, new, (Ordering::AcqRel, Ordering::Acquire)) {
+                Ok(_) => return Some((node, version)),
+                Err(actual) => curr = actual,
+            }
+        }
+    }
+
+    /// Stores a value into the pointer if the current value is the same as
+    /// `current`.
+    ///
+    /// The return value is a result indicating whether the new value was
+    /// written and containing the previous value.
+    /// On success this value is guaranteed to be equal to `current`.
+    ///
+    /// `compare_exchange` takes takes two [`Ordering`] arguments to describe
+    /// the memory ordering of this operation.
+    /// The first describes the required ordering if the operation succeeds
+    /// while the second describes the required ordering when the operation
+    /// fails.
+    /// Using [`Acquire`][acq] as success ordering makes store part of this
+    /// operation [`Relaxed`][rlx], and using [`Release`][rel] makes the
+    /// successful load [`Relaxed`][rlx].
+    /// The failure ordering can only be [`SeqCst`][seq_cst], [`Acquire`][acq]
+    /// or [`Relaxed`][rlx] and must be equivalent or weaker than the success
+    /// ordering.
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only* if the failure ordering is invalid or
+    /// stronger than the success ordering.
+    ///
+    /// [rlx]: Ordering::Relaxed
+    /// [acq]: Ordering::Acquire
+    /// [rel]: Ordering::Release
+    /// [seq_cst]: Ordering::SeqCst
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: TagPtr<T, N>,
+        new: TagPtr<T, N>,
+        (success, failure): (Ordering, Ordering),
+    ) -> Result<TagPtr<T, N>, TagPtr<T, N>> {
+        crate::debug_assert_cas_orderings(success, failure);
+        self.inner
+            .compare_exchange(current.into_raw(), new.into_raw(), success, failure)
+            .map(|_| current)
+            .map_err(TagPtr::new)
+    }
+
+    /// Stores a value into the pointer if the current value is the same as
+    /// `current`.
+    ///
+    /// The return value is a result indicating whether the new value was
+    /// written and containing the previous value.
+    /// On success this value is guaranteed to be equal to `current`.
+    ///
+    /// Unlike `compare_exchange`, this function is allowed to spuriously fail,
+    /// even when the comparison succeeds, which can result in more efficient
+    /// code on some platforms.
+    /// The return value is a result indicating whether the new value was
+    /// written and containing the previous value.
+    ///
+    /// `compare_exchange` takes takes two [`Ordering`] arguments to describe
+    /// the memory ordering of this operation.
+    /// The first describes the required ordering if the operation succeeds
+    /// while the second describes the required ordering when the operation
+    /// fails.
+    /// Using [`Acquire`][acq] as success ordering makes store part of this
+    /// operation [`Relaxed`][rlx], and using [`Release`][rel] makes the
+    /// successful load [`Relaxed`][rlx].
+    /// The failure ordering can only be [`SeqCst`][seq_cst], [`Acquire`][acq]
+    /// or [`Relaxed`][rlx] and must be equivalent or weaker than the success
+    /// ordering.
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only* if the failure ordering is invalid or
+    /// stronger than the success ordering.
+    ///
+    /// [rlx]: Ordering::Relaxed
+    /// [acq]: Ordering::Acquire
+    /// [rel]: Ordering::Release
+    /// [seq_cst]: Ordering::SeqCst
+    #[inline]
+    pub fn compare_exchange_weak(
+        &self,
+        current: TagPtr<T, N>,
+        new: TagPtr<T, N>,
+        (success, failure): (Ordering, Ordering),
+    ) -> Result<TagPtr<T, N>, TagPtr<T, N>> {
+        crate::debug_assert_cas_orderings(success, failure);
+        self.inner
+            .compare_exchange_weak(current.into_raw(), new.into_raw(), success, failure)
+            .map(|_| current)
+            .map_err(TagPtr::new)
+    }
+
+    /// Stores a value into the pointer if the current value is the same as
+    /// `current`.
+    ///
+    /// The return value is always the previous value.
+    /// If it is equal to `current`, then the value was updated.
+    ///
+    /// This is a convenience wrapper around
+    /// [`compare_exchange`][AtomicTagPtr::compare_exchange] with the same
+    /// semantics as the deprecated `compare_and_swap` method of `core`'s
+    /// atomic types.
+    /// `compare_and_swap` takes a single [`Ordering`] argument for the success
+    /// case, from which the strongest permissible failure ordering is derived.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::null();
+    ///
+    /// let prev = ptr.compare_and_swap(TagPtr::null(), TagPtr::new(reference), Ordering::Relaxed);
+    /// assert!(prev.is_null());
+    /// assert_eq!(ptr.load(Ordering::Relaxed), TagPtr::new(reference));
+    /// ```
+    #[inline]
+    pub fn compare_and_swap(
+        &self,
+        current: TagPtr<T, N>,
+        new: TagPtr<T, N>,
+        order: Ordering,
+    ) -> TagPtr<T, N> {
+        match self.compare_exchange(current, new, (order, crate::load_ordering(order))) {
+            Ok(prev) | Err(prev) => prev,
+        }
+    }
+
+    /// Stores a value into the pointer if the current value is the same as
+    /// `current`, returning the previous value in its decomposed form.
+    ///
+    /// This is equivalent to [`compare_exchange`][AtomicTagPtr::compare_exchange]
+    /// except that both the success and the failure value are decomposed into
+    /// the raw pointer and the separated tag value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// const RELAXED: (Ordering, Ordering) = (Ordering::Relaxed, Ordering::Relaxed);
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b01));
+    ///
+    /// let curr = TagPtr::new(reference);
+    /// let res = ptr.compare_exchange_decompose(curr, TagPtr::null(), RELAXED);
+    /// assert_eq!(res, Err((reference as *mut _, 0b01)));
+    ///
+    /// let curr = curr.set_tag(0b01);
+    /// let res = ptr.compare_exchange_decompose(curr, curr.set_tag(0b10), RELAXED);
+    /// assert_eq!(res, Ok((reference as *mut _, 0b01)));
+    /// ```
+    #[inline]
+    pub fn compare_exchange_decompose(
+        &self,
+        current: TagPtr<T, N>,
+        new: TagPtr<T, N>,
+        (success, failure): (Ordering, Ordering),
+    ) -> Result<(*mut T, usize), (*mut T, usize)> {
+        self.compare_exchange(current, new, (success, failure))
+            .map(TagPtr::decompose)
+            .map_err(TagPtr::decompose)
+    }
+
+    /// Stores a value into the pointer if the current pointer bits are the same
+    /// as `current_ptr`, regardless of the current tag value.
+    ///
+    /// The return value is a result indicating whether the new value was
+    /// written.
+    /// On success, it contains the previous tag value, otherwise the current
+    /// value.
+    ///
+    /// `compare_exchange_any_tag` takes two [`Ordering`] arguments to describe
+    /// the memory ordering of this operation, with the same restrictions as
+    /// for [`compare_exchange`][AtomicTagPtr::compare_exchange].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::{ptr, sync::atomic::Ordering};
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// const RELAXED: (Ordering, Ordering) = (Ordering::Relaxed, Ordering::Relaxed);
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b10));
+    ///
+    /// let res = ptr.compare_exchange_any_tag(ptr::null_mut(), TagPtr::null(), RELAXED);
+    /// assert_eq!(res, Err(TagPtr::compose(reference, 0b10)));
+    ///
+    /// let res = ptr.compare_exchange_any_tag(reference, TagPtr::null(), RELAXED);
+    /// assert_eq!(res, Ok(0b10));
+    /// assert!(ptr.load(Ordering::Relaxed).is_null());
+    /// ```
+    #[inline]
+    pub fn compare_exchange_any_tag(
+        &self,
+        current_ptr: *mut T,
+        new: TagPtr<T, N>,
+        (success, failure): (Ordering, Ordering),
+    ) -> Result<usize, TagPtr<T, N>> {
+        crate::debug_assert_cas_orderings(success, failure);
+        let new = new.into_raw();
+        self.inner
+            .fetch_update(success, failure, |curr| {
+                if crate::decompose_ptr(curr, Self::TAG_BITS) == current_ptr {
+                    Some(new)
+                } else {
+                    None
+                }
+            })
+            .map(|prev| crate::decompose_tag(prev.addr(), Self::TAG_BITS))
+            .map_err(TagPtr::new)
+    }
+
+    /// Replaces the tag value with `new_tag` if the current tag value is the
+    /// same as `current_tag`, regardless of the current pointer bits, which
+    /// remain unchanged.
+    ///
+    /// The return value is a result indicating whether the new tag value was
+    /// written.
+    /// On success, it contains the previous tag value, otherwise the current
+    /// value.
+    ///
+    /// `compare_exchange_tag` takes two [`Ordering`] arguments to describe the
+    /// memory ordering of this operation, with the same restrictions as for
+    /// [`compare_exchange`][AtomicTagPtr::compare_exchange].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// const RELAXED: (Ordering, Ordering) = (Ordering::Relaxed, Ordering::Relaxed);
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b01));
+    ///
+    /// let res = ptr.compare_exchange_tag(0b10, 0b11, RELAXED);
+    /// assert_eq!(res, Err(TagPtr::compose(reference, 0b01)));
+    ///
+    /// let res = ptr.compare_exchange_tag(0b01, 0b11, RELAXED);
+    /// assert_eq!(res, Ok(0b01));
+    /// assert_eq!(ptr.load(Ordering::Relaxed), TagPtr::compose(reference, 0b11));
+    /// ```
+    #[inline]
+    pub fn compare_exchange_tag(
+        &self,
+        current_tag: usize,
+        new_tag: usize,
+        (success, failure): (Ordering, Ordering),
+    ) -> Result<usize, TagPtr<T, N>> {
+        crate::debug_assert_cas_orderings(success, failure);
+        let new_tag = new_tag & Self::TAG_MASK;
+        self.inner
+            .fetch_update(success, failure, |curr| {
+                if crate::decompose_tag(curr.addr(), Self::TAG_BITS) == current_tag {
+                    Some(curr.map_addr(|addr| (addr & Self::POINTER_MASK) | new_tag))
+                } else {
+                    None
+                }
+            })
+            .map(|prev| crate::decompose_tag(prev.addr(), Self::TAG_BITS))
+            .map_err(TagPtr::new)
+    }
+
+    /// Stores `node` composed with the tag value `stamp` into the pointer if
+    /// the current value is the same as `expected`.
+    ///
+    /// This allows publishing (handing off) a node to other threads together
+    /// with e.g. a generation stamp in a single atomic operation.
+    /// If the current value does not equal `expected`, it is returned as
+    /// error and the pointer remains unchanged.
+    ///
+    /// `hand_off` takes two [`Ordering`] arguments to describe the memory
+    /// ordering of this operation, with the same restrictions as for
+    /// [`compare_exchange`][AtomicTagPtr::compare_exchange].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::{ptr::NonNull, sync::atomic::Ordering};
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let ptr = AtomicTagPtr::null();
+    /// let node = NonNull::from(&mut 1);
+    ///
+    /// let res = ptr.hand_off(TagPtr::null(), node, 0b10, (Ordering::Release, Ordering::Relaxed));
+    /// assert_eq!(res, Ok(()));
+    /// assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (node.as_ptr(), 0b10));
+    /// ```
+    #[inline]
+    pub fn hand_off(
+        &self,
+        expected: TagPtr<T, N>,
+        node: NonNull<T>,
+        stamp: usize,
+        (success, failure): (Ordering, Ordering),
+    ) -> Result<(), TagPtr<T, N>> {
+        let new = TagPtr::compose(node.as_ptr(), stamp);
+        self.compare_exchange(expected, new, (success, failure)).map(|_| ())
+    }
+
+    /// Stores `node` composed with the tag value `new_epoch` into the pointer
+    /// if the current tag value is `expected_epoch`, regardless of the current
+    /// pointer bits.
+    ///
+    /// This combines publishing a node with advancing an epoch (or generation)
+    /// counter stored in the tag bits in a single atomic operation.
+    /// If the current epoch is stale, i.e., does not equal `expected_epoch`,
+    /// the current value is returned as error and the pointer remains
+    /// unchanged.
+    ///
+    /// `publish_epoch` takes two [`Ordering`] arguments to describe the memory
+    /// ordering of this operation, with the same restrictions as for
+    /// [`compare_exchange`][AtomicTagPtr::compare_exchange].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::{ptr::NonNull, sync::atomic::Ordering};
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// const ORDERS: (Ordering, Ordering) = (Ordering::Release, Ordering::Relaxed);
+    ///
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(&mut 1, 0b01));
+    /// let node = NonNull::from(&mut 2);
+    ///
+    /// let curr = ptr.load(Ordering::Relaxed);
+    /// assert_eq!(ptr.publish_epoch(node, 0b00, 0b10, ORDERS), Err(curr));
+    /// assert_eq!(ptr.publish_epoch(node, 0b01, 0b10, ORDERS), Ok(()));
+    /// assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (node.as_ptr(), 0b10));
+    /// ```
+    #[inline]
+    pub fn publish_epoch(
+        &self,
+        node: NonNull<T>,
+        expected_epoch: usize,
+        new_epoch: usize,
+        (success, failure): (Ordering, Ordering),
+    ) -> Result<(), TagPtr<T, N>> {
+        crate::debug_assert_cas_orderings(success, failure);
+        let new = crate::compose::<T, N>(node.as_ptr(), new_epoch);
+        self.inner
+            .fetch_update(success, failure, |curr| match curr.addr() & Self::TAG_MASK {
+                tag if tag == expected_epoch => Some(new),
+                _ => None,
+            })
+            .map(|_| ())
+            .map_err(TagPtr::new)
+    }
+
+    /// Fetches the value, and applies a function to it that returns an
+    /// optional new value.
+    /// Returns a [`Result`] of `Ok(previous_value)` if the function returned
+    /// `Some(_)`, else `Err(previous_value)`.
+    ///
+    /// The function `func` may be called multiple times if the value has been
+    /// changed from other threads in the meantime, as long as the function
+    /// returns `Some(_)`, but the function will have been applied only once to
+    /// the stored value.
+    ///
+    /// `fetch_update` takes two [`Ordering`] arguments to describe the memory
+    /// ordering of this operation.
+    /// The first describes the required ordering for when the operation
+    /// finally succeeds while the second describes the required ordering for
+    /// loads.
+    /// These correspond to the success and failure orderings of
+    /// [`compare_exchange`][AtomicTagPtr::compare_exchange] respectively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// const RELAXED: (Ordering, Ordering) = (Ordering::Relaxed, Ordering::Relaxed);
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::new(reference));
+    ///
+    /// let res = ptr.fetch_update(RELAXED, |ptr| Some(ptr.set_tag(0b11)));
+    /// assert_eq!(res, Ok(TagPtr::new(reference)));
+    ///
+    /// let res = ptr.fetch_update(RELAXED, |ptr| ptr.is_null().then(TagPtr::null));
+    /// assert_eq!(res, Err(TagPtr::compose(reference, 0b11)));
+    /// ```
+    #[inline]
+    pub fn fetch_update<F>(
+        &self,
+        (set_order, fetch_order): (Ordering, Ordering),
+        mut func: F,
+    ) -> Result<TagPtr<T, N>, TagPtr<T, N>>
+    where
+        F: FnMut(TagPtr<T, N>) -> Option<TagPtr<T, N>>,
+    {
+        crate::debug_assert_cas_orderings(set_order, fetch_order);
+        self.inner
+            .fetch_update(set_order, fetch_order, |curr| {
+                func(TagPtr::new(curr)).map(TagPtr::into_raw)
+            })
+            .map(TagPtr::new)
+            .map_err(TagPtr::new)
+    }
+
+    /// Fetches the value, and applies a function to it that returns an
+    /// optional new value, returning both the result and the number of
+    /// attempted compare-exchange operations.
+    ///
+    /// The function `func` may be called multiple times if the value has been
+    /// changed from other threads in the meantime, as long as the function
+    /// returns `Some(_)`.
+    /// If `func` returns `None`, the operation is aborted and the currently
+    /// loaded value is returned as error.
+    /// The attempt count is the number of (weak) compare-exchange operations
+    /// that were issued, which allows measuring contention without any
+    /// external instrumentation.
+    /// It is always at least 1 on success and may be 0 if `func` aborts
+    /// immediately.
+    ///
+    /// `fetch_update_counted` takes two [`Ordering`] arguments to describe the
+    /// memory ordering of this operation.
+    /// The first describes the required ordering for when the operation
+    /// finally succeeds while the second describes the required ordering for
+    /// loads.
+    /// These correspond to the success and failure orderings of
+    /// [`compare_exchange`][AtomicTagPtr::compare_exchange] respectively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::new(reference));
+    ///
+    /// let (res, attempts) = ptr.fetch_update_counted(
+    ///     (Ordering::Relaxed, Ordering::Relaxed),
+    ///     |ptr| Some(ptr.set_tag(0b11)),
+    /// );
+    ///
+    /// assert_eq!(res, Ok(TagPtr::new(reference)));
+    /// assert!(attempts >= 1);
+    /// ```
+    #[inline]
+    pub fn fetch_update_counted<F>(
+        &self,
+        (set_order, fetch_order): (Ordering, Ordering),
+        mut func: F,
+    ) -> (Result<TagPtr<T, N>, TagPtr<T, N>>, u32)
+    where
+        F: FnMut(TagPtr<T, N>) -> Option<TagPtr<T, N>>,
+    {
+        crate::debug_assert_cas_orderings(set_order, fetch_order);
+        let mut attempts = 0;
+        let mut prev = self.load(fetch_order);
+        while let Some(next) = func(prev) {
+            attempts += 1;
+            match self.compare_exchange_weak(prev, next, (set_order, fetch_order)) {
+                Ok(prev) => return (Ok(prev), attempts),
+                Err(curr) => prev = curr,
+            }
+        }
+
+        (Err(prev), attempts)
+    }
+
+    /// Fetches the value, and applies a function to its pointer bits only,
+    /// which returns an optional new pointer, while the current tag value is
+    /// preserved.
+    /// Returns a [`Result`] of `Ok(previous_ptr)` if the function returned
+    /// `Some(_)`, else `Err(current_ptr)`.
+    ///
+    /// The function `func` may be called multiple times if the value has been
+    /// changed from other threads in the meantime, as long as the function
+    /// returns `Some(_)`.
+    /// The tag value is re-read on every attempt, so concurrent tag updates
+    /// are never overwritten.
+    ///
+    /// `fetch_update_ptr` takes two [`Ordering`] arguments with the same
+    /// meaning as for [`fetch_update_counted`][AtomicTagPtr::fetch_update_counted].
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only*, if `func` returns a pointer with any of
+    /// its tag bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let array = &mut [1, 2, 3];
+    /// let cursor = AtomicTagPtr::new(TagPtr::compose(array.as_mut_ptr(), 0b01));
+    ///
+    /// let res = cursor.fetch_update_ptr(
+    ///     (Ordering::Relaxed, Ordering::Relaxed),
+    ///     |ptr| Some(ptr.wrapping_add(1)),
+    /// );
+    ///
+    /// assert_eq!(res, Ok(array.as_mut_ptr()));
+    /// assert_eq!(
+    ///     cursor.load(Ordering::Relaxed).decompose(),
+    ///     (array.as_mut_ptr().wrapping_add(1), 0b01)
+    /// );
+    /// ```
+    #[inline]
+    pub fn fetch_update_ptr<F>(
+        &self,
+        (set_order, fetch_order): (Ordering, Ordering),
+        mut func: F,
+    ) -> Result<*mut T, *mut T>
+    where
+        F: FnMut(*mut T) -> Option<*mut T>,
+    {
+        crate::debug_assert_cas_orderings(set_order, fetch_order);
+        self.inner
+            .fetch_update(set_order, fetch_order, |curr| {
+                let tag = crate::decompose_tag(curr.addr(), Self::TAG_BITS);
+                let ptr = func(crate::decompose_ptr(curr, Self::TAG_BITS))?;
+                Some(crate::compose::<T, N>(ptr, tag))
+            })
+            .map(|prev| crate::decompose_ptr(prev, Self::TAG_BITS))
+            .map_err(|curr| crate::decompose_ptr(curr, Self::TAG_BITS))
+    }
+
+    /// Fetches the value, and applies a function to its decomposed pointer
+    /// and tag value, which returns an optional new pair of pointer and tag
+    /// value.
+    /// Returns a [`Result`] of `Ok(previous_parts)` if the function returned
+    /// `Some(_)`, else `Err(current_parts)`.
+    ///
+    /// The function `func` may be called multiple times if the value has been
+    /// changed from other threads in the meantime, as long as the function
+    /// returns `Some(_)`.
+    /// Any excess bits of the returned tag value are truncated.
+    ///
+    /// `update_parts` takes two [`Ordering`] arguments with the same meaning
+    /// as for [`fetch_update_counted`][AtomicTagPtr::fetch_update_counted].
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only*, if `func` returns a pointer with any of
+    /// its tag bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let (a, b) = (&mut 1, &mut 2);
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(a, 0b01));
+    ///
+    /// let res = ptr.update_parts((Ordering::Relaxed, Ordering::Relaxed), |_, tag| {
+    ///     Some((b as *mut _, tag + 1))
+    /// });
+    ///
+    /// assert_eq!(res, Ok((a as *mut _, 0b01)));
+    /// assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (b as *mut _, 0b10));
+    /// ```
+    #[inline]
+    pub fn update_parts<F>(
+        &self,
+        (set_order, fetch_order): (Ordering, Ordering),
+        mut func: F,
+    ) -> Result<(*mut T, usize), (*mut T, usize)>
+    where
+        F: FnMut(*mut T, usize) -> Option<(*mut T, usize)>,
+    {
+        crate::debug_assert_cas_orderings(set_order, fetch_order);
+        self.inner
+            .fetch_update(set_order, fetch_order, |curr| {
+                let (ptr, tag) = TagPtr::<T, N>::new(curr).decompose();
+                let (ptr, tag) = func(ptr, tag)?;
+                Some(crate::compose::<T, N>(ptr, tag))
+            })
+            .map(|prev| TagPtr::<T, N>::new(prev).decompose())
+            .map_err(|curr| TagPtr::<T, N>::new(curr).decompose())
+    }
+
+    /// Adds `value` to the current tag value, returning the previous marked
+    /// pointer.
+    ///
+    /// This operation directly and unconditionally alters the internal numeric
+    /// representation of the atomic marked pointer.
+    /// Hence there is no way to reliably guarantee the operation only affects
+    /// the tag bits and does not overflow into the pointer bits.
+    ///
+    /// `fetch_add` takes takes an [`Ordering`] argument which describes the
+    /// memory ordering of this operation.
+    /// All ordering modes are possible.
+    /// Note that using [`Acquire`][acq] makes the store part of this operation
+    /// [`Relaxed`][rlx] and using [`Release`][rel] makes the load part
+    /// [`Relaxed`][rlx].
+    ///
+    /// [rlx]: Ordering::Relaxed
+    /// [acq]: Ordering::Acquire
+    /// [rel]: Ordering::Release
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::new(reference));
+    ///
+    /// assert_eq!(
+    ///     ptr.fetch_add(1, Ordering::Relaxed).decompose(),
+    ///     (reference as *mut _, 0)
+    /// );
+    ///
+    /// assert_eq!(
+    ///     ptr.load(Ordering::Relaxed).decompose(),
+    ///     (reference as *mut _, 0b01)
+    /// );
+    /// ```
+    #[inline]
+    pub fn fetch_add(&self, value: usize, order: Ordering) -> TagPtr<T, N> {
+        debug_check!(value < Self::TAG_MASK, "`value` exceeds tag bits (would overflow)");
+        TagPtr::new(self.inner.fetch_byte_add(value, order))
+    }
+
+    /// Subtracts `value` from the current tag value, returning the previous
+    /// marked pointer.
+    ///
+    /// This operation directly and unconditionally alters the internal numeric
+    /// representation of the atomic marked pointer.
+    /// Hence there is no way to reliably guarantee the operation only affects
+    /// the tag bits and does not overflow into the pointer bits.
+    ///
+    /// `fetch_sub` takes takes an [`Ordering`] argument which describes the
+    /// memory ordering of this operation.
+    /// All ordering modes are possible.
+    /// Note that using [`Acquire`][acq] makes the store part of this operation
+    /// [`Relaxed`][rlx] and using [`Release`][rel] makes the load part
+    /// [`Relaxed`][rlx].
+    ///
+    /// [rlx]: Ordering::Relaxed
+    /// [acq]: Ordering::Acquire
+    /// [rel]: Ordering::Release
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b10));
     ///
     /// assert_eq!(
     ///     ptr.fetch_sub(1, Ordering::Relaxed).decompose(),
@@ -315,8 +1382,122 @@ impl<T, const N: usize> AtomicTagPtr<T, N> {
     /// ```
     #[inline]
     pub fn fetch_sub(&self, value: usize, order: Ordering) -> TagPtr<T, N> {
-        debug_assert!(value < Self::TAG_MASK, "`value` exceeds tag bits (would underflow)");
-        TagPtr::from_usize(self.inner.fetch_sub(value, order))
+        debug_check!(value < Self::TAG_MASK, "`value` exceeds tag bits (would underflow)");
+        TagPtr::new(self.inner.fetch_byte_sub(value, order))
+    }
+
+    /// Adds `value` to the current tag value, wrapping around at the boundary
+    /// of the tag bits, and returns the previous marked pointer.
+    ///
+    /// Unlike [`fetch_add`][AtomicTagPtr::fetch_add], the sum is masked into
+    /// the tag bits, so that the pointer bits are never affected.
+    /// E.g., for 2 tag bits, adding 1 to a tag value of `0b11` results in a
+    /// tag value of `0b00`.
+    /// Since there is no masked atomic addition, this operation is implemented
+    /// as a compare-exchange loop.
+    ///
+    /// `fetch_add_tag` takes an [`Ordering`] argument which describes the
+    /// memory ordering of this operation.
+    /// All ordering modes are possible.
+    /// Note that using [`Acquire`][acq] makes the store part of this operation
+    /// [`Relaxed`][rlx] and using [`Release`][rel] makes the load part
+    /// [`Relaxed`][rlx].
+    ///
+    /// [rlx]: Ordering::Relaxed
+    /// [acq]: Ordering::Acquire
+    /// [rel]: Ordering::Release
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b11));
+    ///
+    /// assert_eq!(ptr.fetch_add_tag(1, Ordering::Relaxed).decompose_tag(), 0b11);
+    /// assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0b00));
+    /// ```
+    #[inline]
+    pub fn fetch_add_tag(&self, value: usize, order: Ordering) -> TagPtr<T, N> {
+        self.fetch_update_tag(order, |tag| tag.wrapping_add(value))
+    }
+
+    /// Subtracts `value` from the current tag value, wrapping around at the
+    /// boundary of the tag bits, and returns the previous marked pointer.
+    ///
+    /// Unlike [`fetch_sub`][AtomicTagPtr::fetch_sub], the difference is masked
+    /// into the tag bits, so that the pointer bits are never affected.
+    /// E.g., for 2 tag bits, subtracting 1 from a tag value of `0b00` results
+    /// in a tag value of `0b11`.
+    /// Since there is no masked atomic subtraction, this operation is
+    /// implemented as a compare-exchange loop.
+    ///
+    /// `fetch_sub_tag` takes an [`Ordering`] argument with the same meaning as
+    /// for [`fetch_add_tag`][AtomicTagPtr::fetch_add_tag].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::new(reference));
+    ///
+    /// assert_eq!(ptr.fetch_sub_tag(1, Ordering::Relaxed).decompose_tag(), 0b00);
+    /// assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0b11));
+    /// ```
+    #[inline]
+    pub fn fetch_sub_tag(&self, value: usize, order: Ordering) -> TagPtr<T, N> {
+        self.fetch_update_tag(order, |tag| tag.wrapping_sub(value))
+    }
+
+    /// Decrements the current tag value, which is interpreted as a reference
+    /// count, by one and returns the previous marked pointer and whether the
+    /// count has reached zero.
+    ///
+    /// The tag value must not already be zero.
+    /// If it is, the decrement saturates and leaves the marked pointer
+    /// unchanged rather than borrowing from the pointer bits.
+    ///
+    /// `fetch_dec_refcount_tag` takes an [`Ordering`] argument which describes
+    /// the memory ordering of this operation.
+    /// All ordering modes are possible.
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only* if the previous tag value was zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 2));
+    ///
+    /// let (prev, is_zero) = ptr.fetch_dec_refcount_tag(Ordering::Release);
+    /// assert_eq!((prev.decompose_tag(), is_zero), (2, false));
+    /// let (prev, is_zero) = ptr.fetch_dec_refcount_tag(Ordering::Release);
+    /// assert_eq!((prev.decompose_tag(), is_zero), (1, true));
+    ///
+    /// assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0));
+    /// ```
+    #[inline]
+    pub fn fetch_dec_refcount_tag(&self, order: Ordering) -> (TagPtr<T, N>, bool) {
+        let prev = self.fetch_update_tag(order, |tag| tag.saturating_sub(1));
+        let count = prev.decompose_tag();
+        debug_check_ne!(count, 0, "tag reference count must not already be zero");
+        (prev, count == 1)
     }
 
     /// Performs a bitwise "or" of `value` with the current tag value, returning
@@ -334,9 +1515,220 @@ impl<T, const N: usize> AtomicTagPtr<T, N> {
     /// [`Relaxed`][rlx] and using [`Release`][rel] makes the load part
     /// [`Relaxed`][rlx].
     ///
-    /// [rlx]: Ordering::Relaxed
-    /// [acq]: Ordering::Acquire
-    /// [rel]: Ordering::Release
+    /// [rlx]: Ordering::Relaxed
+    /// [acq]: Ordering::Acquire
+    /// [rel]: Ordering::Release
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b10));
+    ///
+    /// assert_eq!(
+    ///     ptr.fetch_or(0b11, Ordering::Relaxed).decompose(),
+    ///     (reference as *mut _, 0b10)
+    /// );
+    ///
+    /// assert_eq!(
+    ///     ptr.load(Ordering::Relaxed).decompose(),
+    ///     (reference as *mut _, 0b11)
+    /// );
+    /// ```
+    #[inline]
+    pub fn fetch_or(&self, value: usize, order: Ordering) -> TagPtr<T, N> {
+        debug_check!(value <= Self::TAG_MASK, "`value` exceeds tag bits (would corrupt pointer)");
+        TagPtr::new(self.inner.fetch_or(Self::TAG_MASK & value, order))
+    }
+
+    /// Performs a bitwise "and" of `value` with the current tag value,
+    /// returning the previous marked pointer.
+    ///
+    /// This operation directly and unconditionally alters the internal numeric
+    /// representation of the atomic marked pointer.
+    /// Hence there is no way to reliably guarantee the operation only affects
+    /// the tag bits and does not overflow into the pointer bits.
+    ///
+    /// `fetch_and` takes takes an [`Ordering`] argument which describes the
+    /// memory ordering of this operation.
+    /// All ordering modes are possible.
+    /// Note that using [`Acquire`][acq] makes the store part of this operation
+    /// [`Relaxed`][rlx] and using [`Release`][rel] makes the load part
+    /// [`Relaxed`][rlx].
+    ///
+    /// [rlx]: Ordering::Relaxed
+    /// [acq]: Ordering::Acquire
+    /// [rel]: Ordering::Release
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b10));
+    ///
+    /// // fetch_x returns previous value
+    /// assert_eq!(
+    ///     ptr.fetch_and(0b11, Ordering::Relaxed).decompose(),
+    ///     (reference as *mut _, 0b10)
+    /// );
+    ///
+    /// assert_eq!(
+    ///     ptr.load(Ordering::Relaxed).decompose(),
+    ///     (reference as *mut _, 0b10)
+    /// );
+    /// ```
+    #[inline]
+    pub fn fetch_and(&self, value: usize, order: Ordering) -> TagPtr<T, N> {
+        debug_check!(value <= Self::TAG_MASK, "`value` exceeds tag bits (would corrupt pointer)");
+        TagPtr::new(self.inner.fetch_and(Self::POINTER_MASK | value, order))
+    }
+
+    /// Performs a bitwise "or" of `mask` with the current tag value, returning
+    /// the previous marked pointer.
+    ///
+    /// Unlike [`fetch_or`][AtomicTagPtr::fetch_or], any bits of `mask`
+    /// exceeding the tag bits are silently discarded, so that the pointer bits
+    /// are never affected.
+    ///
+    /// `fetch_or_tag` takes an [`Ordering`] argument with the same meaning as
+    /// for [`fetch_or`][AtomicTagPtr::fetch_or].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b01));
+    ///
+    /// assert_eq!(ptr.fetch_or_tag(0b110, Ordering::Relaxed).decompose_tag(), 0b01);
+    /// assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0b11));
+    /// ```
+    #[inline]
+    pub fn fetch_or_tag(&self, mask: usize, order: Ordering) -> TagPtr<T, N> {
+        TagPtr::new(self.inner.fetch_or(Self::TAG_MASK & mask, order))
+    }
+
+    /// Performs a bitwise "and" of `mask` with the current tag value, returning
+    /// the previous marked pointer.
+    ///
+    /// Unlike [`fetch_and`][AtomicTagPtr::fetch_and], any bits of `mask`
+    /// exceeding the tag bits are silently discarded, so that the pointer bits
+    /// are never affected.
+    ///
+    /// `fetch_and_tag` takes an [`Ordering`] argument with the same meaning as
+    /// for [`fetch_and`][AtomicTagPtr::fetch_and].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b11));
+    ///
+    /// assert_eq!(ptr.fetch_and_tag(0b101, Ordering::Relaxed).decompose_tag(), 0b11);
+    /// assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0b01));
+    /// ```
+    #[inline]
+    pub fn fetch_and_tag(&self, mask: usize, order: Ordering) -> TagPtr<T, N> {
+        TagPtr::new(self.inner.fetch_and(Self::POINTER_MASK | (Self::TAG_MASK & mask), order))
+    }
+
+    /// Performs a bitwise "xor" of `mask` with the current tag value, returning
+    /// the previous marked pointer.
+    ///
+    /// Any bits of `mask` exceeding the tag bits are silently discarded, so
+    /// that the pointer bits are never affected.
+    ///
+    /// `fetch_xor_tag` takes an [`Ordering`] argument with the same meaning as
+    /// for [`fetch_or`][AtomicTagPtr::fetch_or].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b01));
+    ///
+    /// assert_eq!(ptr.fetch_xor_tag(0b111, Ordering::Relaxed).decompose_tag(), 0b01);
+    /// assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0b10));
+    /// ```
+    #[inline]
+    pub fn fetch_xor_tag(&self, mask: usize, order: Ordering) -> TagPtr<T, N> {
+        TagPtr::new(self.inner.fetch_xor(Self::TAG_MASK & mask, order))
+    }
+
+    /// Sets the tag bit at index `bit` and returns `true` if it was already
+    /// set before.
+    ///
+    /// All other tag bits and the pointer bits are left unchanged.
+    /// This is typically used for logically deleting a node by marking the
+    /// pointer to it, where only the thread observing the bit as previously
+    /// unset may proceed with its removal.
+    ///
+    /// `test_and_set_tag_bit` takes an [`Ordering`] argument with the same
+    /// meaning as for [`fetch_or`][AtomicTagPtr::fetch_or].
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only* if `bit` is not within the tag bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::new(reference));
+    ///
+    /// assert!(!ptr.test_and_set_tag_bit(1, Ordering::AcqRel));
+    /// assert!(ptr.test_and_set_tag_bit(1, Ordering::AcqRel));
+    /// assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0b10));
+    /// ```
+    #[inline]
+    pub fn test_and_set_tag_bit(&self, bit: u32, order: Ordering) -> bool {
+        debug_check!((bit as usize) < Self::TAG_BITS, "`bit` exceeds tag bits");
+        self.fetch_or_tag(1 << bit, order).decompose_tag() & (1 << bit) != 0
+    }
+
+    /// Sets the tag bit at index `bit`, if the current pointer bits are the
+    /// same as `current_ptr` and the bit is not already set.
+    ///
+    /// This is typically used for logically deleting a specific node by
+    /// marking the pointer to it.
+    /// All other tag bits are left unchanged.
+    ///
+    /// The return value is a result indicating whether the bit was set.
+    /// If the pointer did not match or the bit was already set, the current
+    /// value is returned as error.
+    ///
+    /// `mark_if_ptr` takes two [`Ordering`] arguments to describe the memory
+    /// ordering of this operation, with the same restrictions as for
+    /// [`compare_exchange`][AtomicTagPtr::compare_exchange].
     ///
     /// # Examples
     ///
@@ -346,70 +1738,136 @@ impl<T, const N: usize> AtomicTagPtr<T, N> {
     /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
     /// type TagPtr = tagptr::TagPtr<i32, 2>;
     ///
-    /// let reference = &mut 1;
-    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b10));
+    /// const RELAXED: (Ordering, Ordering) = (Ordering::Relaxed, Ordering::Relaxed);
     ///
-    /// assert_eq!(
-    ///     ptr.fetch_or(0b11, Ordering::Relaxed).decompose(),
-    ///     (reference as *mut _, 0b10)
-    /// );
+    /// let reference = &mut 1;
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b01));
     ///
-    /// assert_eq!(
-    ///     ptr.load(Ordering::Relaxed).decompose(),
-    ///     (reference as *mut _, 0b11)
-    /// );
+    /// assert_eq!(ptr.mark_if_ptr(reference, 1, RELAXED), Ok(()));
+    /// assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0b11));
+    /// assert_eq!(ptr.mark_if_ptr(reference, 1, RELAXED), Err(TagPtr::compose(reference, 0b11)));
     /// ```
     #[inline]
-    pub fn fetch_or(&self, value: usize, order: Ordering) -> TagPtr<T, N> {
-        debug_assert!(value <= Self::TAG_MASK, "`value` exceeds tag bits (would corrupt pointer)");
-        TagPtr::from_usize(self.inner.fetch_or(Self::TAG_MASK & value, order))
+    pub fn mark_if_ptr(
+        &self,
+        current_ptr: *mut T,
+        bit: u32,
+        (success, failure): (Ordering, Ordering),
+    ) -> Result<(), TagPtr<T, N>> {
+        debug_check!((bit as usize) < Self::TAG_BITS, "`bit` exceeds tag bits");
+        crate::debug_assert_cas_orderings(success, failure);
+        let mask = 1 << bit;
+        self.inner
+            .fetch_update(success, failure, |curr| {
+                let matches = crate::decompose_ptr(curr, Self::TAG_BITS) == current_ptr;
+                if matches && curr.addr() & mask == 0 {
+                    Some(curr.map_addr(|addr| addr | mask))
+                } else {
+                    None
+                }
+            })
+            .map(|_| ())
+            .map_err(TagPtr::new)
     }
 
-    /// Performs a bitwise "and" of `value` with the current tag value,
+    /// Sets the pointer bits to `null` if the tag bit at index `bit` is set,
     /// returning the previous marked pointer.
     ///
-    /// This operation directly and unconditionally alters the internal numeric
-    /// representation of the atomic marked pointer.
-    /// Hence there is no way to reliably guarantee the operation only affects
-    /// the tag bits and does not overflow into the pointer bits.
+    /// This is typically used for physically unlinking a node after it has
+    /// been logically deleted by marking it.
+    /// The tag value itself is left unchanged.
     ///
-    /// `fetch_and` takes takes an [`Ordering`] argument which describes the
-    /// memory ordering of this operation.
-    /// All ordering modes are possible.
-    /// Note that using [`Acquire`][acq] makes the store part of this operation
-    /// [`Relaxed`][rlx] and using [`Release`][rel] makes the load part
-    /// [`Relaxed`][rlx].
+    /// The return value is a result indicating whether the pointer was
+    /// `null`ed.
+    /// If the bit was not set, the current value is returned as error.
     ///
-    /// [rlx]: Ordering::Relaxed
-    /// [acq]: Ordering::Acquire
-    /// [rel]: Ordering::Release
+    /// `null_if_marked` takes two [`Ordering`] arguments to describe the memory
+    /// ordering of this operation, with the same restrictions as for
+    /// [`compare_exchange`][AtomicTagPtr::compare_exchange].
     ///
     /// # Examples
     ///
     /// ```
-    /// use core::sync::atomic::Ordering;
+    /// use core::{ptr, sync::atomic::Ordering};
     ///
     /// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
     /// type TagPtr = tagptr::TagPtr<i32, 2>;
     ///
     /// let reference = &mut 1;
-    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b10));
+    /// let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b01));
     ///
-    /// // fetch_x returns previous value
-    /// assert_eq!(
-    ///     ptr.fetch_and(0b11, Ordering::Relaxed).decompose(),
-    ///     (reference as *mut _, 0b10)
-    /// );
+    /// let res = ptr.null_if_marked(1, (Ordering::Relaxed, Ordering::Relaxed));
+    /// assert_eq!(res, Err(TagPtr::compose(reference, 0b01)));
     ///
-    /// assert_eq!(
-    ///     ptr.load(Ordering::Relaxed).decompose(),
-    ///     (reference as *mut _, 0b10)
-    /// );
+    /// let res = ptr.null_if_marked(0, (Ordering::Relaxed, Ordering::Relaxed));
+    /// assert_eq!(res, Ok(TagPtr::compose(reference, 0b01)));
+    /// assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (ptr::null_mut(), 0b01));
     /// ```
     #[inline]
-    pub fn fetch_and(&self, value: usize, order: Ordering) -> TagPtr<T, N> {
-        debug_assert!(value <= Self::TAG_MASK, "`value` exceeds tag bits (would corrupt pointer)");
-        TagPtr::from_usize(self.inner.fetch_and(Self::POINTER_MASK | value, order))
+    pub fn null_if_marked(
+        &self,
+        bit: u32,
+        (success, failure): (Ordering, Ordering),
+    ) -> Result<TagPtr<T, N>, TagPtr<T, N>> {
+        debug_check!((bit as usize) < Self::TAG_BITS, "`bit` exceeds tag bits");
+        crate::debug_assert_cas_orderings(success, failure);
+        let mask = 1 << bit;
+        self.inner
+            .fetch_update(success, failure, |curr| match curr.addr() & mask {
+                0 => None,
+                _ => Some(curr.map_addr(|addr| addr & Self::TAG_MASK)),
+            })
+            .map(TagPtr::new)
+            .map_err(TagPtr::new)
+    }
+
+    /// Replaces the current tag value with the (masked) result of `func` in a
+    /// compare-exchange loop, returning the previous marked pointer.
+    #[inline]
+    fn fetch_update_tag(&self, order: Ordering, func: impl Fn(usize) -> usize) -> TagPtr<T, N> {
+        let res = self.inner.fetch_update(order, crate::load_ordering(order), |curr| {
+            let (ptr, tag) = TagPtr::<T, N>::new(curr).decompose();
+            Some(crate::compose::<T, N>(ptr, func(tag)))
+        });
+
+        match res {
+            Ok(prev) | Err(prev) => TagPtr::new(prev),
+        }
+    }
+}
+
+/********** impl inherent (SeqGuard) **************************************************************/
+
+impl<T, const N: usize> SeqGuard<'_, T, N> {
+    /// Returns the marked pointer snapshot taken when the guard was created.
+    #[inline]
+    pub fn snapshot(&self) -> TagPtr<T, N> {
+        self.snapshot
+    }
+
+    /// Returns the sequence counter (i.e., the tag value) of the snapshot.
+    #[inline]
+    pub fn sequence(&self) -> usize {
+        self.snapshot.decompose_tag()
+    }
+
+    /// Returns `true` if the sequence counter of the snapshot is odd, i.e., a
+    /// write was in progress when the guard was created.
+    #[inline]
+    pub fn is_writing(&self) -> bool {
+        self.sequence() & 1 == 1
+    }
+
+    /// Returns `true` if the atomic marked pointer has not been changed since
+    /// the guard was created, i.e., all reads performed in the meantime are
+    /// consistent.
+    ///
+    /// The result is only meaningful if [`is_writing`][SeqGuard::is_writing]
+    /// returned `false`.
+    #[inline]
+    pub fn validate(&self) -> bool {
+        fence(Ordering::Acquire);
+        self.atomic.load(Ordering::Relaxed) == self.snapshot
     }
 }
 
@@ -455,3 +1913,660 @@ impl<T, const N: usize> fmt::Pointer for AtomicTagPtr<T, N> {
         fmt::Pointer::fmt(&self.load(Ordering::SeqCst), f)
     }
 }
+
+/********** impl LoomExt (loom::sync::atomic::AtomicPtr<T>) ***************************************/
+
+/// Substitutes for those operations of `core`'s `AtomicPtr`, which are not
+/// provided by `loom`'s atomic pointer type.
+#[cfg(loom)]
+trait LoomExt<T> {
+    fn fetch_map_addr(&self, order: Ordering, func: impl Fn(usize) -> usize) -> *mut T;
+
+    fn fetch_byte_add(&self, value: usize, order: Ordering) -> *mut T {
+        self.fetch_map_addr(order, |addr| addr.wrapping_add(value))
+    }
+
+    fn fetch_byte_sub(&self, value: usize, order: Ordering) -> *mut T {
+        self.fetch_map_addr(order, |addr| addr.wrapping_sub(value))
+    }
+
+    fn fetch_or(&self, value: usize, order: Ordering) -> *mut T {
+        self.fetch_map_addr(order, |addr| addr | value)
+    }
+
+    fn fetch_and(&self, value: usize, order: Ordering) -> *mut T {
+        self.fetch_map_addr(order, |addr| addr & value)
+    }
+
+    fn fetch_xor(&self, value: usize, order: Ordering) -> *mut T {
+        self.fetch_map_addr(order, |addr| addr ^ value)
+    }
+}
+
+#[cfg(loom)]
+impl<T> LoomExt<T> for AtomicPtr<T> {
+    #[inline]
+    fn fetch_map_addr(&self, order: Ordering, func: impl Fn(usize) -> usize) -> *mut T {
+        let res =
+            self.fetch_update(order, crate::load_ordering(order), |ptr| Some(ptr.map_addr(&func)));
+        match res {
+            Ok(prev) | Err(prev) => prev,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        ptr::{self, NonNull},
+        sync::atomic::Ordering,
+    };
+
+    type AtomicTagPtr = crate::AtomicTagPtr<i32, 2>;
+    type TagPtr = crate::TagPtr<i32, 2>;
+
+    const RELAXED: (Ordering, Ordering) = (Ordering::Relaxed, Ordering::Relaxed);
+
+    #[cfg(all(debug_assertions, not(feature = "no-panic")))]
+    mod orderings {
+        use core::sync::atomic::Ordering::{self, *};
+
+        use super::{AtomicTagPtr, TagPtr};
+
+        fn compare_exchange(success: Ordering, failure: Ordering) {
+            let _ = AtomicTagPtr::null().compare_exchange(
+                TagPtr::null(),
+                TagPtr::null(),
+                (success, failure),
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "there is no such thing as a release load")]
+        fn load_release() {
+            AtomicTagPtr::null().load(Release);
+        }
+
+        #[test]
+        #[should_panic(expected = "there is no such thing as a release load")]
+        fn load_acq_rel() {
+            AtomicTagPtr::null().load(AcqRel);
+        }
+
+        #[test]
+        #[should_panic(expected = "there is no such thing as an acquire store")]
+        fn store_acquire() {
+            AtomicTagPtr::null().store(TagPtr::null(), Acquire);
+        }
+
+        #[test]
+        #[should_panic(expected = "there is no such thing as an acquire store")]
+        fn store_acq_rel() {
+            AtomicTagPtr::null().store(TagPtr::null(), AcqRel);
+        }
+
+        #[test]
+        #[should_panic(expected = "there is no such thing as a release failure ordering")]
+        fn cas_failure_release() {
+            compare_exchange(SeqCst, Release);
+        }
+
+        #[test]
+        #[should_panic(expected = "there is no such thing as a release failure ordering")]
+        fn cas_failure_acq_rel() {
+            compare_exchange(SeqCst, AcqRel);
+        }
+
+        #[test]
+        #[should_panic(expected = "a failure ordering can't be stronger than a success ordering")]
+        fn cas_failure_acquire_stronger() {
+            compare_exchange(Release, Acquire);
+        }
+
+        #[test]
+        #[should_panic(expected = "a failure ordering can't be stronger than a success ordering")]
+        fn cas_failure_seq_cst_stronger() {
+            compare_exchange(AcqRel, SeqCst);
+        }
+
+        #[test]
+        #[should_panic(expected = "a failure ordering can't be stronger than a success ordering")]
+        fn null_if_marked_failure_stronger() {
+            let _ = AtomicTagPtr::null().null_if_marked(0, (Relaxed, Acquire));
+        }
+
+        #[test]
+        #[should_panic(expected = "there is no such thing as a release failure ordering")]
+        fn fetch_update_ptr_failure_release() {
+            let _ = AtomicTagPtr::null().fetch_update_ptr((SeqCst, Release), Some);
+        }
+
+        #[test]
+        #[should_panic(expected = "a failure ordering can't be stronger than a success ordering")]
+        fn fetch_update_counted_failure_stronger() {
+            let _ = AtomicTagPtr::null().fetch_update_counted((Relaxed, SeqCst), |_| None);
+        }
+
+        #[test]
+        #[should_panic(expected = "a failure ordering can't be stronger than a success ordering")]
+        fn update_parts_failure_stronger() {
+            let _ =
+                AtomicTagPtr::null().update_parts((Release, Acquire), |ptr, tag| Some((ptr, tag)));
+        }
+    }
+
+    #[test]
+    fn test_compare_exchange() {
+        let reference = &mut 1;
+        let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b01));
+
+        let (current, new) = (TagPtr::compose(reference, 0b10), TagPtr::null());
+        assert_eq!(
+            ptr.compare_exchange(current, new, RELAXED),
+            Err(TagPtr::compose(reference, 0b01))
+        );
+        assert_eq!(
+            ptr.compare_exchange_weak(current, new, RELAXED),
+            Err(TagPtr::compose(reference, 0b01))
+        );
+
+        let current = TagPtr::compose(reference, 0b01);
+        assert_eq!(ptr.compare_exchange(current, new, RELAXED), Ok(current));
+        assert!(ptr.load(Ordering::Relaxed).is_null());
+
+        let mut res = ptr.compare_exchange_weak(new, current, RELAXED);
+        while let Err(actual) = res {
+            assert_eq!(actual, new);
+            res = ptr.compare_exchange_weak(new, current, RELAXED);
+        }
+
+        assert_eq!(res, Ok(new));
+        assert_eq!(ptr.load(Ordering::Relaxed), current);
+    }
+
+    #[test]
+    fn test_hand_off_contended() {
+        let (a, b) = (&mut 1, &mut 2);
+        let ptr = AtomicTagPtr::new(TagPtr::compose(a, 0b01));
+
+        // another thread has handed off a different node in the meantime
+        let expected = TagPtr::new(a);
+        let res = ptr.hand_off(expected, NonNull::from(b), 0b10, RELAXED);
+        assert_eq!(res, Err(TagPtr::compose(a, 0b01)));
+        assert_eq!(ptr.load(Ordering::Relaxed), TagPtr::compose(a, 0b01));
+    }
+
+    #[test]
+    fn test_compare_and_swap() {
+        let (a, b) = (&mut 1, &mut 2);
+        let ptr = AtomicTagPtr::new(TagPtr::compose(a, 0b01));
+
+        let prev = ptr.compare_and_swap(TagPtr::compose(a, 0b01), TagPtr::new(b), Ordering::AcqRel);
+        assert_eq!(prev, TagPtr::compose(a, 0b01));
+        assert_eq!(ptr.load(Ordering::Relaxed), TagPtr::new(b));
+
+        // another thread has swapped the value in the meantime
+        let prev =
+            ptr.compare_and_swap(TagPtr::compose(a, 0b01), TagPtr::null(), Ordering::Release);
+        assert_eq!(prev, TagPtr::new(b));
+        assert_eq!(ptr.load(Ordering::Relaxed), TagPtr::new(b));
+    }
+
+    #[test]
+    fn test_compare_exchange_any_tag() {
+        let (a, b) = (&mut 1, &mut 2);
+        let ptr = AtomicTagPtr::null();
+
+        for tag in 0..=0b11 {
+            ptr.store(TagPtr::compose(a, tag), Ordering::Relaxed);
+            let res = ptr.compare_exchange_any_tag(a, TagPtr::compose(b, 0b01), RELAXED);
+            assert_eq!(res, Ok(tag));
+
+            let res = ptr.compare_exchange_any_tag(a, TagPtr::null(), RELAXED);
+            assert_eq!(res, Err(TagPtr::compose(b, 0b01)));
+        }
+    }
+
+    #[test]
+    fn test_compare_exchange_tag() {
+        let (a, b) = (&mut 1, &mut 2);
+        let ptr = AtomicTagPtr::new(TagPtr::compose(a, 0b01));
+        let expected = ptr.load(Ordering::Relaxed);
+
+        // the pointer changes under us, but the tag still matches
+        ptr.store(TagPtr::compose(b, 0b01), Ordering::Relaxed);
+        let res = ptr.compare_exchange_tag(expected.decompose_tag(), 0b10, RELAXED);
+        assert_eq!(res, Ok(0b01));
+        assert_eq!(ptr.load(Ordering::Relaxed), TagPtr::compose(b, 0b10));
+
+        let res = ptr.compare_exchange_tag(0b01, 0b11, RELAXED);
+        assert_eq!(res, Err(TagPtr::compose(b, 0b10)));
+        assert_eq!(ptr.load(Ordering::Relaxed), TagPtr::compose(b, 0b10));
+    }
+
+    #[test]
+    fn test_fetch_update_ptr() {
+        let array = &mut [1, 2, 3];
+        let base = array.as_mut_ptr();
+        let cursor = AtomicTagPtr::new(TagPtr::compose(base, 0b10));
+
+        for i in 0..2 {
+            let res = cursor.fetch_update_ptr(RELAXED, |ptr| Some(ptr.wrapping_add(1)));
+            assert_eq!(res, Ok(base.wrapping_add(i)));
+        }
+
+        let end = base.wrapping_add(2);
+        let res = cursor.fetch_update_ptr(RELAXED, |ptr| (ptr != end).then(|| ptr.wrapping_add(1)));
+        assert_eq!(res, Err(end));
+        assert_eq!(cursor.load(Ordering::Relaxed).decompose(), (end, 0b10));
+    }
+
+    #[test]
+    #[cfg(all(debug_assertions, not(feature = "no-panic")))]
+    #[should_panic(expected = "tag bits in raw pointer must be zeroed")]
+    fn test_fetch_update_ptr_misaligned() {
+        let reference = &mut 1;
+        let ptr = AtomicTagPtr::new(TagPtr::new(reference));
+        let _ = ptr.fetch_update_ptr(RELAXED, |ptr| Some(ptr.cast::<u8>().wrapping_add(1).cast()));
+    }
+
+    #[test]
+    fn test_static() {
+        static FOO: AtomicTagPtr = AtomicTagPtr::null();
+        static BAR: AtomicTagPtr = AtomicTagPtr::new(TagPtr::from_usize(0b10));
+
+        assert_eq!(FOO.load(Ordering::Relaxed).into_usize(), 0);
+        assert_eq!(BAR.load(Ordering::Relaxed).decompose(), (ptr::null_mut(), 0b10));
+
+        FOO.store(TagPtr::compose(ptr::null_mut(), 0b01), Ordering::Relaxed);
+        assert_eq!(FOO.swap(TagPtr::null(), Ordering::Relaxed).decompose_tag(), 0b01);
+        assert_eq!(AtomicTagPtr::new(FOO.load(Ordering::Relaxed)).into_inner(), TagPtr::null());
+    }
+
+    #[test]
+    fn test_atomic_ptr_roundtrip() {
+        let reference = &mut 1;
+        let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b11));
+
+        let raw = ptr.into_atomic_ptr();
+        assert_eq!(raw.load(Ordering::Relaxed), TagPtr::compose(reference, 0b11).into_raw());
+
+        let ptr = AtomicTagPtr::from_atomic_ptr(raw);
+        assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0b11));
+    }
+
+    #[test]
+    fn test_load_store_mut() {
+        let (a, b) = (&mut 1, &mut 2);
+        let mut ptr = AtomicTagPtr::new(TagPtr::compose(a, 0b01));
+        assert_eq!(ptr.load_mut().decompose(), (a as *mut _, 0b01));
+
+        ptr.store_mut(TagPtr::compose(b, 0b10));
+        assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (b as *mut _, 0b10));
+        assert_eq!(ptr.load_mut(), *ptr.get_mut());
+    }
+
+    #[test]
+    fn test_swap() {
+        let (a, b) = (&mut 1, &mut 2);
+        let ptr = AtomicTagPtr::new(TagPtr::compose(a, 0b11));
+
+        let prev = ptr.swap(TagPtr::compose(b, 0b01), Ordering::Relaxed);
+        assert_eq!(prev.decompose(), (a as *mut _, 0b11));
+        assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (b as *mut _, 0b01));
+    }
+
+    #[test]
+    fn test_swap_and_clear() {
+        let (a, b) = (&mut 1, &mut 2);
+        let ptr = AtomicTagPtr::new(TagPtr::compose(a, 0b10));
+
+        let prev = ptr.swap_and_clear(TagPtr::compose(b, 0b01), Ordering::Relaxed);
+        assert_eq!(prev.decompose(), (a as *mut _, 0));
+        // the stored value keeps its tag
+        assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (b as *mut _, 0b01));
+    }
+
+    #[test]
+    fn test_load_store() {
+        let reference = &mut 1;
+        let ptr = AtomicTagPtr::null();
+
+        ptr.store(TagPtr::compose(reference, 0b11), Ordering::SeqCst);
+        assert_eq!(ptr.load(Ordering::SeqCst), TagPtr::compose(reference, 0b11));
+        assert_eq!(ptr.load(Ordering::SeqCst).decompose(), (reference as *mut _, 0b11));
+    }
+
+    #[test]
+    fn test_checked_store() {
+        use core::ptr;
+
+        use crate::MisalignedError;
+
+        type AtomicTagPtr = crate::AtomicTagPtr<u64, 2>;
+        type TagPtr = crate::TagPtr<u64, 2>;
+
+        let reference = &mut 1;
+        let ptr = AtomicTagPtr::null();
+
+        assert_eq!(ptr.checked_store(TagPtr::compose(reference, 0b11), Ordering::Relaxed), Ok(()));
+        assert_eq!(ptr.load(Ordering::Relaxed), TagPtr::compose(reference, 0b11));
+        assert_eq!(ptr.checked_store(TagPtr::null(), Ordering::Relaxed), Ok(()));
+        assert!(ptr.load(Ordering::Relaxed).is_null());
+
+        let misaligned = (reference as *mut u64).cast::<u8>().wrapping_add(4).cast::<u64>();
+        let res = ptr.checked_store(TagPtr::compose(misaligned, 0b01), Ordering::Relaxed);
+        assert_eq!(res, Err(MisalignedError(misaligned as usize)));
+        assert!(ptr.load(Ordering::Relaxed).is_null());
+
+        let res =
+            ptr.checked_store(TagPtr::new(ptr::without_provenance_mut(0x1004)), Ordering::Relaxed);
+        assert_eq!(res, Err(MisalignedError(0x1004)));
+        assert_eq!(std::format!("{}", res.unwrap_err()), "pointer is misaligned (address: 0x1004)");
+    }
+
+    #[test]
+    fn test_load_until() {
+        let reference = &mut 1;
+        let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b01));
+
+        let res = ptr.load_until(|ptr| ptr.decompose_tag() == 0b01, 0, Ordering::Relaxed);
+        assert_eq!(res, Some(TagPtr::compose(reference, 0b01)));
+
+        let loads = core::cell::Cell::new(0);
+        let res = ptr.load_until(
+            |ptr| {
+                loads.set(loads.get() + 1);
+                ptr.is_null()
+            },
+            10,
+            Ordering::Relaxed,
+        );
+
+        assert_eq!(res, None);
+        assert_eq!(loads.get(), 11);
+    }
+
+    #[test]
+    fn test_take_decompose() {
+        let reference = &mut 1;
+        let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b11));
+
+        assert_eq!(ptr.take_decompose(Ordering::Relaxed), (reference as *mut _, 0b11));
+        assert_eq!(ptr.load(Ordering::Relaxed).into_usize(), 0);
+        assert_eq!(ptr.take_decompose(Ordering::Relaxed), (ptr::null_mut(), 0));
+
+        ptr.store(TagPtr::compose(ptr::null_mut(), 0b01), Ordering::Relaxed);
+        assert_eq!(ptr.take_decompose(Ordering::Relaxed), (ptr::null_mut(), 0b01));
+        assert_eq!(ptr.load(Ordering::Relaxed).into_usize(), 0);
+    }
+
+    #[test]
+    fn test_fetch_add_sub_tag() {
+        let reference = &mut 1;
+        let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b11));
+
+        assert_eq!(ptr.fetch_add_tag(1, Ordering::Relaxed), TagPtr::compose(reference, 0b11));
+        assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0b00));
+        assert_eq!(ptr.fetch_add_tag(0b110, Ordering::Relaxed).decompose_tag(), 0b00);
+        assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0b10));
+
+        assert_eq!(ptr.fetch_sub_tag(0b11, Ordering::AcqRel).decompose_tag(), 0b10);
+        assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0b11));
+        assert_eq!(ptr.fetch_sub_tag(0b11, Ordering::Release).decompose_tag(), 0b11);
+        assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0b00));
+    }
+
+    #[test]
+    fn test_fetch_bitwise_tag() {
+        let reference = &mut 1;
+        let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b01));
+
+        assert_eq!(ptr.fetch_or_tag(0b10, Ordering::Relaxed), TagPtr::compose(reference, 0b01));
+        assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0b11));
+        assert_eq!(ptr.fetch_and_tag(!0b01, Ordering::Relaxed).decompose_tag(), 0b11);
+        assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0b10));
+        assert_eq!(ptr.fetch_xor_tag(usize::MAX, Ordering::Relaxed).decompose_tag(), 0b10);
+        assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0b01));
+        assert_eq!(ptr.fetch_or_tag(!0b11, Ordering::Relaxed).decompose_tag(), 0b01);
+        assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0b01));
+    }
+
+    #[test]
+    fn test_test_and_set_tag_bit() {
+        let reference = &mut 1;
+        let ptr = AtomicTagPtr::new(TagPtr::compose(reference, 0b10));
+
+        assert!(!ptr.test_and_set_tag_bit(0, Ordering::Relaxed));
+        assert!(ptr.test_and_set_tag_bit(0, Ordering::Relaxed));
+        assert!(ptr.test_and_set_tag_bit(1, Ordering::Relaxed));
+        assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (reference as *mut _, 0b11));
+    }
+
+    #[test]
+    #[cfg(all(debug_assertions, not(feature = "no-panic")))]
+    #[should_panic(expected = "`bit` exceeds tag bits")]
+    fn test_test_and_set_tag_bit_out_of_range() {
+        let _ = AtomicTagPtr::null().test_and_set_tag_bit(2, Ordering::Relaxed);
+    }
+
+    #[test]
+    fn test_update_parts() {
+        let array = &mut [1, 2];
+        let base = array.as_mut_ptr();
+        let ptr = AtomicTagPtr::new(TagPtr::compose(base, 0b11));
+
+        let res = ptr.update_parts(RELAXED, |ptr, tag| Some((ptr.wrapping_add(1), tag - 1)));
+        assert_eq!(res, Ok((base, 0b11)));
+        assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (base.wrapping_add(1), 0b10));
+
+        let res = ptr.update_parts(RELAXED, |ptr, tag| Some((ptr, tag + 0b110)));
+        assert_eq!(res, Ok((base.wrapping_add(1), 0b10)));
+        assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (base.wrapping_add(1), 0b00));
+
+        let res = ptr.update_parts(RELAXED, |ptr, tag| (tag != 0).then_some((ptr, 0)));
+        assert_eq!(res, Err((base.wrapping_add(1), 0b00)));
+    }
+
+    #[test]
+    fn test_mark_if_ptr() {
+        let (a, b) = (&mut 1, &mut 2);
+        let (a, b): (*mut i32, *mut i32) = (a, b);
+        let ptr = AtomicTagPtr::new(TagPtr::compose(a, 0b10));
+
+        // pointer mismatch
+        assert_eq!(ptr.mark_if_ptr(b, 0, RELAXED), Err(TagPtr::compose(a, 0b10)));
+        // already marked
+        assert_eq!(ptr.mark_if_ptr(a, 1, RELAXED), Err(TagPtr::compose(a, 0b10)));
+        // match
+        assert_eq!(ptr.mark_if_ptr(a, 0, RELAXED), Ok(()));
+        assert_eq!(ptr.load(Ordering::Relaxed), TagPtr::compose(a, 0b11));
+    }
+
+    #[test]
+    fn test_fetch_update() {
+        let reference = &mut 1;
+        let ptr = AtomicTagPtr::new(TagPtr::new(reference));
+
+        for tag in 0..TagPtr::TAG_MASK {
+            let res = ptr.fetch_update(RELAXED, |ptr| {
+                assert_eq!(ptr.decompose_ptr(), reference as *mut _);
+                (!ptr.tag_is_saturated()).then(|| ptr.add_tag(1))
+            });
+            assert_eq!(res, Ok(TagPtr::compose(reference, tag)));
+        }
+
+        let res =
+            ptr.fetch_update(RELAXED, |ptr| (!ptr.tag_is_saturated()).then(|| ptr.add_tag(1)));
+        assert_eq!(res, Err(TagPtr::compose(reference, TagPtr::TAG_MASK)));
+    }
+
+    #[test]
+    fn test_fetch_update_counted() {
+        let reference = &mut 1;
+        let ptr = AtomicTagPtr::new(TagPtr::new(reference));
+
+        let (res, attempts) = ptr.fetch_update_counted(RELAXED, |ptr| Some(ptr.add_tag(1)));
+        assert_eq!(res, Ok(TagPtr::new(reference)));
+        assert!(attempts >= 1);
+
+        let (res, attempts) = ptr.fetch_update_counted(RELAXED, |_| None);
+        assert_eq!(res, Err(TagPtr::compose(reference, 0b01)));
+        assert_eq!(attempts, 0);
+    }
+
+    #[test]
+    fn test_drain_list() {
+        use std::{boxed::Box, vec::Vec};
+
+        struct Node {
+            next: crate::TagPtr<Node, 2>,
+            value: i32,
+        }
+
+        let list = crate::AtomicTagPtr::null();
+        for value in 0..3 {
+            let next = list.load(Ordering::Relaxed);
+            let node = Box::into_raw(Box::new(Node { next, value }));
+            list.store(crate::TagPtr::compose(node, value as usize), Ordering::Relaxed);
+        }
+
+        let next_of = |node: NonNull<Node>| unsafe { node.as_ref().next };
+        let values: Vec<_> = list
+            .drain_list(next_of, Ordering::Acquire)
+            .map(|node| unsafe { Box::from_raw(node.as_ptr()).value })
+            .collect();
+
+        assert_eq!(values, [2, 1, 0]);
+        assert!(list.load(Ordering::Relaxed).is_null());
+        assert_eq!(list.drain_list(next_of, Ordering::Acquire).count(), 0);
+    }
+
+    #[test]
+    fn test_pop_versioned() {
+        struct Node {
+            next: crate::TagPtr<Node, 2>,
+            value: i32,
+        }
+
+        let mut c = Node { next: crate::TagPtr::null(), value: 3 };
+        let mut b = Node { next: crate::TagPtr::compose(&mut c, 0b11), value: 2 };
+        let mut a = Node { next: crate::TagPtr::new(&mut b), value: 1 };
+
+        let stack = crate::AtomicTagPtr::new(crate::TagPtr::compose(&mut a, 0b10));
+        let next_of = |node: NonNull<Node>| unsafe { node.as_ref().next };
+
+        let mut popped = [(0, 0); 3];
+        for (i, entry) in popped.iter_mut().enumerate() {
+            let (node, version) = stack.pop_versioned(next_of).unwrap();
+            *entry = (unsafe { node.as_ref().value }, version);
+            assert_eq!(stack.load(Ordering::Relaxed).decompose_tag(), version);
+            assert_eq!(version, (0b10 + i + 1) & 0b11);
+        }
+
+        assert_eq!(popped.map(|(value, _)| value), [1, 2, 3]);
+        assert!(stack.pop_versioned(next_of).is_none());
+        assert_eq!(stack.load(Ordering::Relaxed), crate::TagPtr::compose(ptr::null_mut(), 0b01));
+    }
+
+    #[cfg(not(miri))]
+    #[test]
+    fn test_pop_versioned_concurrent() {
+        use std::{sync::Mutex, thread, vec::Vec};
+
+        const NODES: usize = 1_000;
+        const THREADS: usize = 4;
+
+        struct Node {
+            next: crate::TagPtr<Node, 3>,
+            value: usize,
+        }
+
+        // all nodes outlive the stack, so `next_of` never reads freed memory
+        let mut nodes: Vec<_> =
+            (0..NODES).map(|value| Node { next: crate::TagPtr::null(), value }).collect();
+        let base = nodes.as_mut_ptr();
+        for i in 0..NODES - 1 {
+            unsafe { (*base.add(i)).next = crate::TagPtr::new(base.add(i + 1)) };
+        }
+
+        let stack = crate::AtomicTagPtr::new(crate::TagPtr::new(base));
+        let next_of = |node: NonNull<Node>| unsafe { node.as_ref().next };
+        let popped = Mutex::new(Vec::with_capacity(NODES));
+
+        thread::scope(|s| {
+            for _ in 0..THREADS {
+                s.spawn(|| {
+                    let mut local = Vec::new();
+                    while let Some((node, _)) = stack.pop_versioned(next_of) {
+                        local.push(unsafe { node.as_ref().value });
+                    }
+
+                    popped.lock().unwrap().extend(local);
+                });
+            }
+        });
+
+        let mut popped = popped.into_inner().unwrap();
+        popped.sort_unstable();
+        assert!(popped.into_iter().eq(0..NODES));
+        assert_eq!(stack.load(Ordering::Relaxed).decompose_tag(), NODES & 0b111);
+    }
+
+    #[test]
+    fn test_seq_guard() {
+        let reference = &mut 1;
+        let ptr = AtomicTagPtr::new(TagPtr::new(reference));
+
+        let guard = ptr.seq_guard();
+        assert_eq!(guard.snapshot(), TagPtr::new(reference));
+        assert_eq!(guard.sequence(), 0);
+        assert!(!guard.is_writing());
+        assert!(guard.validate());
+
+        ptr.fetch_add(1, Ordering::Relaxed);
+        assert!(!guard.validate());
+        assert!(ptr.seq_guard().is_writing());
+    }
+
+    #[cfg(not(miri))]
+    #[test]
+    fn test_seq_guard_concurrent() {
+        use core::sync::atomic::{self, AtomicUsize};
+        use std::thread;
+
+        const WRITES: usize = 1_000;
+
+        // the sequence counter is allowed to overflow into the (null) pointer
+        // bits, which rules out any ABA issues for this test
+        let seq = crate::AtomicTagPtr::<i32, 2>::null();
+        let data = [AtomicUsize::new(0), AtomicUsize::new(0)];
+
+        thread::scope(|s| {
+            s.spawn(|| {
+                for i in 1..=WRITES {
+                    seq.fetch_add(1, Ordering::Relaxed);
+                    atomic::fence(Ordering::Release);
+                    data[0].store(i, Ordering::Relaxed);
+                    data[1].store(i, Ordering::Relaxed);
+                    seq.fetch_add(1, Ordering::Release);
+                }
+            });
+
+            s.spawn(|| loop {
+                let guard = seq.seq_guard();
+                if guard.is_writing() {
+                    continue;
+                }
+
+                let (a, b) = (data[0].load(Ordering::Relaxed), data[1].load(Ordering::Relaxed));
+                if guard.validate() {
+                    assert_eq!(a, b);
+                    if a == WRITES {
+                        break;
+                    }
+                }
+            });
+        });
+    }
+}
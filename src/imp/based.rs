@@ -0,0 +1,246 @@
+use core::{
+    cmp, fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+use crate::BasedTagPtr;
+
+/********** impl Clone ****************************************************************************/
+
+impl<T, const N: usize> Clone for BasedTagPtr<T, N> {
+    impl_clone!();
+}
+
+/********** impl Copy *****************************************************************************/
+
+impl<T, const N: usize> Copy for BasedTagPtr<T, N> {}
+
+/********** impl inherent *************************************************************************/
+
+impl<T, const N: usize> BasedTagPtr<T, N> {
+    doc_comment! {
+        doc_tag_bits!(),
+        pub const TAG_BITS: usize = N;
+    }
+
+    doc_comment! {
+        doc_tag_mask!(),
+        pub const TAG_MASK: usize = crate::mark_mask(Self::TAG_BITS);
+    }
+
+    doc_comment! {
+        doc_ptr_mask!(),
+        pub const POINTER_MASK: usize = !Self::TAG_MASK;
+    }
+
+    /// Creates a new unmarked pointer from the given `offset`.
+    #[inline]
+    pub const fn new(offset: usize) -> Self {
+        Self { inner: offset, _marker: PhantomData }
+    }
+
+    doc_comment! {
+        doc_from_usize!(),
+        #[inline]
+        pub const fn from_usize(val: usize) -> Self {
+            Self::new(val)
+        }
+    }
+
+    doc_comment! {
+        doc_into_usize!(),
+        #[inline]
+        pub const fn into_usize(self) -> usize {
+            self.inner
+        }
+    }
+
+    /// Composes a new marked pointer from an `offset` and a `tag` value.
+    ///
+    /// The supplied `offset` is assumed to be well-aligned (i.e. has no tag
+    /// bits set) and calling this function may lead to unexpected results
+    /// when this is not the case.
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only* if `offset` is not well aligned.
+    #[inline]
+    pub fn compose(offset: usize, tag: usize) -> Self {
+        debug_check_eq!(offset & Self::TAG_MASK, 0, "tag bits in offset must be zeroed");
+        Self::new(offset | (tag & Self::TAG_MASK))
+    }
+
+    /// Composes a new marked pointer from the offset of `ptr` relative to
+    /// `base` and a `tag` value.
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only* if `ptr` is located before `base` or if
+    /// the resulting offset is not well aligned.
+    #[inline]
+    pub fn from_ptr(base: *mut u8, ptr: *mut T, tag: usize) -> Self {
+        debug_check!(ptr.addr() >= base.addr(), "`ptr` must not be located before `base`");
+        Self::compose(ptr.addr().wrapping_sub(base.addr()), tag)
+    }
+
+    /// Resolves the marked pointer relative to the given `base` address,
+    /// returning the raw pointer stripped of its tag.
+    ///
+    /// The returned pointer inherits the provenance of `base`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type BasedTagPtr = tagptr::BasedTagPtr<u64, 3>;
+    ///
+    /// let mut region = [0u64, 1, 2, 3];
+    /// let raw = region.as_mut_ptr();
+    /// let base = raw.cast::<u8>();
+    ///
+    /// let ptr = BasedTagPtr::from_ptr(base, raw.wrapping_add(2), 0b101);
+    /// assert_eq!(ptr.decompose(), (16, 0b101));
+    /// assert_eq!(unsafe { *ptr.resolve(base) }, 2);
+    /// ```
+    #[inline]
+    pub fn resolve(self, base: *mut u8) -> *mut T {
+        base.wrapping_add(self.decompose_offset()).cast()
+    }
+
+    doc_comment! {
+        doc_clear_tag!(),
+        #[inline]
+        pub fn clear_tag(self) -> Self {
+            Self::new(self.decompose_offset())
+        }
+    }
+
+    doc_comment! {
+        doc_split_tag!(),
+        #[inline]
+        pub fn split_tag(self) -> (Self, usize) {
+            let (offset, tag) = self.decompose();
+            (Self::new(offset), tag)
+        }
+    }
+
+    doc_comment! {
+        doc_set_tag!(),
+        #[inline]
+        pub fn set_tag(self, tag: usize) -> Self {
+            Self::compose(self.decompose_offset(), tag)
+        }
+    }
+
+    doc_comment! {
+        doc_update_tag!(),
+        #[inline]
+        pub fn update_tag(self, func: impl FnOnce(usize) -> usize) -> Self {
+            let (offset, tag) = self.decompose();
+            Self::compose(offset, func(tag))
+        }
+    }
+
+    /// Decomposes the marked pointer, returning the offset and the separated
+    /// tag value.
+    #[inline]
+    pub const fn decompose(self) -> (usize, usize) {
+        (self.decompose_offset(), self.decompose_tag())
+    }
+
+    /// Decomposes the marked pointer, returning only the separated offset.
+    #[inline]
+    pub const fn decompose_offset(self) -> usize {
+        self.inner & Self::POINTER_MASK
+    }
+
+    doc_comment! {
+        doc_decompose_tag!(),
+        #[inline]
+        pub const fn decompose_tag(self) -> usize {
+            crate::decompose_tag(self.inner, Self::TAG_BITS)
+        }
+    }
+}
+
+/********** impl Debug ****************************************************************************/
+
+impl<T, const N: usize> fmt::Debug for BasedTagPtr<T, N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (offset, tag) = self.decompose();
+        f.debug_struct("BasedTagPtr").field("offset", &offset).field("tag", &tag).finish()
+    }
+}
+
+/********** impl Default **************************************************************************/
+
+impl<T, const N: usize> Default for BasedTagPtr<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+/********** impl PartialEq ************************************************************************/
+
+impl<T, const N: usize> PartialEq for BasedTagPtr<T, N> {
+    impl_partial_eq!();
+}
+
+/********** impl PartialOrd ***********************************************************************/
+
+impl<T, const N: usize> PartialOrd for BasedTagPtr<T, N> {
+    impl_partial_ord!();
+}
+
+/********** impl Eq *******************************************************************************/
+
+impl<T, const N: usize> Eq for BasedTagPtr<T, N> {}
+
+/********** impl Ord ******************************************************************************/
+
+impl<T, const N: usize> Ord for BasedTagPtr<T, N> {
+    impl_ord!();
+}
+
+/********** impl Hash *****************************************************************************/
+
+impl<T, const N: usize> Hash for BasedTagPtr<T, N> {
+    impl_hash!();
+}
+
+#[cfg(test)]
+mod tests {
+    type BasedTagPtr = crate::BasedTagPtr<u64, 3>;
+
+    #[test]
+    fn test_resolve_relocated() {
+        let mut region = [0u64, 1, 2, 3];
+        let mut copy = region;
+
+        let base = region.as_mut_ptr().cast::<u8>();
+        let relocated = copy.as_mut_ptr().cast::<u8>();
+        let ptr = BasedTagPtr::from_ptr(base, base.cast::<u64>().wrapping_add(3), 0b110);
+
+        assert_eq!(ptr.decompose(), (24, 0b110));
+        assert_eq!(ptr.resolve(relocated), relocated.cast::<u64>().wrapping_add(3));
+
+        unsafe {
+            *ptr.resolve(relocated) = 4;
+            assert_eq!(*ptr.resolve(base), 3);
+        }
+
+        assert_eq!(copy, [0, 1, 2, 4]);
+    }
+
+    #[test]
+    fn test_tag_independent_of_base() {
+        let ptr = BasedTagPtr::compose(8, 0b11);
+        assert_eq!(ptr.decompose(), (8, 0b11));
+        assert_eq!(ptr.set_tag(0b1111).decompose(), (8, 0b111));
+        assert_eq!(ptr.clear_tag(), BasedTagPtr::new(8));
+        assert_eq!(ptr.split_tag(), (BasedTagPtr::new(8), 0b11));
+        assert_eq!(ptr.update_tag(|tag| tag + 1).decompose(), (8, 0b100));
+    }
+}
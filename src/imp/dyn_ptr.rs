@@ -0,0 +1,264 @@
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    ptr,
+};
+
+use crate::{DynTagPtr, TagPtr};
+
+/********** impl Clone ****************************************************************************/
+
+impl<T> Clone for DynTagPtr<T> {
+    impl_clone!();
+}
+
+/********** impl Copy *****************************************************************************/
+
+impl<T> Copy for DynTagPtr<T> {}
+
+/********** impl inherent *************************************************************************/
+
+impl<T> DynTagPtr<T> {
+    /// Creates a new unmarked `null` pointer with `bits` tag bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the alignment of `T` is insufficient for storing `bits` tag
+    /// bits.
+    #[inline]
+    pub const fn null(bits: u8) -> Self {
+        Self::new(ptr::null_mut(), bits)
+    }
+
+    /// Creates a new pointer with `bits` tag bits from the (potentially
+    /// marked) raw pointer `ptr` *as is*.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the alignment of `T` is insufficient for storing `bits` tag
+    /// bits, which also rules out any number of bits that could not be
+    /// shifted within a `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// type DynTagPtr = tagptr::DynTagPtr<u16>;
+    ///
+    /// // a `u16` is only guaranteed to have one unused lower bit
+    /// let _ = DynTagPtr::null(2);
+    /// ```
+    #[inline]
+    pub const fn new(ptr: *mut T, bits: u8) -> Self {
+        assert!(
+            crate::has_sufficient_alignment::<T>(bits as usize),
+            "the alignment of `T` is insufficient for storing `bits` tag bits"
+        );
+        Self { inner: ptr, bits }
+    }
+
+    /// Composes a new marked pointer with `bits` tag bits from a raw `ptr` and
+    /// a `tag` value.
+    ///
+    /// The supplied `ptr` is assumed to be well-aligned (i.e. has no tag bits
+    /// set) and any bits of `tag` exceeding the available tag bits are
+    /// silently truncated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the alignment of `T` is insufficient for storing `bits` tag
+    /// bits and in *debug builds only* if `ptr` is not well aligned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type DynTagPtr = tagptr::DynTagPtr<u64>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = DynTagPtr::compose(reference, 0b1101, 3);
+    /// assert_eq!(ptr.decompose(), (reference as *mut _, 0b101));
+    /// ```
+    #[inline]
+    pub fn compose(ptr: *mut T, tag: usize, bits: u8) -> Self {
+        let mask = crate::mark_mask(bits as usize);
+        debug_check_eq!(ptr.addr() & mask, 0, "tag bits in raw pointer must be zeroed");
+        Self::new(ptr.map_addr(|addr| addr | (tag & mask)), bits)
+    }
+
+    /// Returns the number of tag bits.
+    #[inline]
+    pub const fn tag_bits(self) -> u8 {
+        self.bits
+    }
+
+    /// Returns the bitmask for the lower bits of the pointer used for storing
+    /// the tag value.
+    #[inline]
+    pub const fn tag_mask(self) -> usize {
+        crate::mark_mask(self.bits as usize)
+    }
+
+    /// Returns the bitmask for the upper bits of the pointer used for storing
+    /// the pointer value.
+    #[inline]
+    pub const fn pointer_mask(self) -> usize {
+        !self.tag_mask()
+    }
+
+    doc_comment! {
+        doc_into_raw!(),
+        #[inline]
+        pub const fn into_raw(self) -> *mut T {
+            self.inner
+        }
+    }
+
+    /// Returns `true` if the marked pointer is `null` (regardless of its tag).
+    #[inline]
+    pub fn is_null(self) -> bool {
+        self.decompose_ptr().is_null()
+    }
+
+    doc_comment! {
+        doc_clear_tag!(),
+        #[inline]
+        pub fn clear_tag(self) -> Self {
+            Self::new(self.decompose_ptr(), self.bits)
+        }
+    }
+
+    doc_comment! {
+        doc_split_tag!(),
+        #[inline]
+        pub fn split_tag(self) -> (Self, usize) {
+            let (ptr, tag) = self.decompose();
+            (Self::new(ptr, self.bits), tag)
+        }
+    }
+
+    doc_comment! {
+        doc_set_tag!(),
+        #[inline]
+        pub fn set_tag(self, tag: usize) -> Self {
+            Self::compose(self.decompose_ptr(), tag, self.bits)
+        }
+    }
+
+    doc_comment! {
+        doc_update_tag!(),
+        #[inline]
+        pub fn update_tag(self, func: impl FnOnce(usize) -> usize) -> Self {
+            let (ptr, tag) = self.decompose();
+            Self::compose(ptr, func(tag), self.bits)
+        }
+    }
+
+    doc_comment! {
+        doc_decompose!(),
+        #[inline]
+        pub fn decompose(self) -> (*mut T, usize) {
+            (self.decompose_ptr(), self.decompose_tag())
+        }
+    }
+
+    doc_comment! {
+        doc_decompose_ptr!(),
+        #[inline]
+        pub fn decompose_ptr(self) -> *mut T {
+            crate::decompose_ptr(self.inner, self.bits as usize)
+        }
+    }
+
+    doc_comment! {
+        doc_decompose_tag!(),
+        #[inline]
+        pub fn decompose_tag(self) -> usize {
+            crate::decompose_tag(self.inner.addr(), self.bits as usize)
+        }
+    }
+}
+
+/********** impl Debug ****************************************************************************/
+
+impl<T> fmt::Debug for DynTagPtr<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (ptr, tag) = self.decompose();
+        f.debug_struct("DynTagPtr")
+            .field("ptr", &ptr)
+            .field("tag", &tag)
+            .field("bits", &self.bits)
+            .finish()
+    }
+}
+
+/********** impl From (TagPtr) ********************************************************************/
+
+impl<T, const N: usize> From<TagPtr<T, N>> for DynTagPtr<T> {
+    #[inline]
+    fn from(ptr: TagPtr<T, N>) -> Self {
+        Self::new(ptr.into_raw(), N as u8)
+    }
+}
+
+/********** impl PartialEq ************************************************************************/
+
+impl<T> PartialEq for DynTagPtr<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner && self.bits == other.bits
+    }
+}
+
+/********** impl Eq *******************************************************************************/
+
+impl<T> Eq for DynTagPtr<T> {}
+
+/********** impl Hash *****************************************************************************/
+
+impl<T> Hash for DynTagPtr<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+        self.bits.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    type DynTagPtr = crate::DynTagPtr<u64>;
+
+    #[test]
+    fn test_compose_truncates() {
+        let reference = &mut 1;
+        let ptr = DynTagPtr::compose(reference, 0b1111, 3);
+
+        assert_eq!(ptr.tag_mask(), 0b111);
+        assert_eq!(ptr.decompose(), (reference as *mut _, 0b111));
+        assert_eq!(ptr.set_tag(0b1010).decompose(), (reference as *mut _, 0b010));
+        assert_eq!(ptr.update_tag(|tag| tag + 1).decompose(), (reference as *mut _, 0));
+        assert_eq!(ptr.split_tag(), (ptr.clear_tag(), 0b111));
+    }
+
+    #[test]
+    fn test_from_tag_ptr() {
+        let reference = &mut 1;
+        let ptr = DynTagPtr::from(crate::TagPtr::<u64, 2>::compose(reference, 0b10));
+
+        assert_eq!(ptr.tag_bits(), 2);
+        assert_eq!(ptr.decompose(), (reference as *mut _, 0b10));
+        assert_ne!(ptr, DynTagPtr::new(ptr.into_raw(), 3));
+        assert!(DynTagPtr::null(3).set_tag(0b101).is_null());
+    }
+
+    #[test]
+    #[should_panic(expected = "the alignment of `T` is insufficient for storing `bits` tag bits")]
+    fn test_bits_exceed_alignment() {
+        let _ = DynTagPtr::compose(&mut 1, 0, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "the alignment of `T` is insufficient for storing `bits` tag bits")]
+    fn test_bits_exceed_usize() {
+        let _ = DynTagPtr::null(64);
+    }
+}
@@ -0,0 +1,249 @@
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ptr,
+};
+
+use crate::FatTagPtr;
+
+/********** impl Clone ****************************************************************************/
+
+impl<T: ?Sized, const N: usize> Clone for FatTagPtr<T, N> {
+    impl_clone!();
+}
+
+/********** impl Copy *****************************************************************************/
+
+impl<T: ?Sized, const N: usize> Copy for FatTagPtr<T, N> {}
+
+/********** impl inherent *************************************************************************/
+
+impl<T: ?Sized, const N: usize> FatTagPtr<T, N> {
+    doc_comment! {
+        doc_tag_bits!(),
+        pub const TAG_BITS: usize = N;
+    }
+
+    doc_comment! {
+        doc_tag_mask!(),
+        pub const TAG_MASK: usize = crate::mark_mask(Self::TAG_BITS);
+    }
+
+    doc_comment! {
+        doc_ptr_mask!(),
+        pub const POINTER_MASK: usize = !Self::TAG_MASK;
+    }
+
+    doc_comment! {
+        doc_new!(),
+        #[inline]
+        pub const fn new(ptr: *mut T) -> Self {
+            Self { inner: ptr, _marker: PhantomData }
+        }
+    }
+
+    /// Composes a new marked pointer from a raw (potentially fat) `ptr` and a
+    /// `tag` value, tagging only the data pointer half.
+    ///
+    /// The supplied `ptr` is assumed to be well-aligned (i.e. its data pointer
+    /// has no tag bits set) and any bits of `tag` exceeding the available tag
+    /// bits are silently truncated.
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only* if `ptr` is not well aligned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type FatTagPtr = tagptr::FatTagPtr<[u32], 2>;
+    ///
+    /// let slice: &mut [u32] = &mut [1, 2, 3];
+    /// let raw = slice as *mut [u32];
+    ///
+    /// let ptr = FatTagPtr::compose(raw, 0b10);
+    /// assert_eq!(ptr.decompose_tag(), 0b10);
+    /// assert_eq!(unsafe { &*ptr.decompose_ptr() }, &[1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn compose(ptr: *mut T, tag: usize) -> Self {
+        debug_check_eq!(ptr.addr() & Self::TAG_MASK, 0, "tag bits in raw pointer must be zeroed");
+        Self::new(ptr.map_addr(|addr| addr | (tag & Self::TAG_MASK)))
+    }
+
+    doc_comment! {
+        doc_into_raw!(),
+        #[inline]
+        pub const fn into_raw(self) -> *mut T {
+            self.inner
+        }
+    }
+
+    /// Returns `true` if the data pointer is `null` (regardless of its tag).
+    #[inline]
+    pub fn is_null(self) -> bool {
+        self.decompose_ptr().is_null()
+    }
+
+    doc_comment! {
+        doc_clear_tag!(),
+        #[inline]
+        pub fn clear_tag(self) -> Self {
+            Self::new(self.decompose_ptr())
+        }
+    }
+
+    doc_comment! {
+        doc_split_tag!(),
+        #[inline]
+        pub fn split_tag(self) -> (Self, usize) {
+            let (ptr, tag) = self.decompose();
+            (Self::new(ptr), tag)
+        }
+    }
+
+    doc_comment! {
+        doc_set_tag!(),
+        #[inline]
+        pub fn set_tag(self, tag: usize) -> Self {
+            Self::compose(self.decompose_ptr(), tag)
+        }
+    }
+
+    doc_comment! {
+        doc_update_tag!(),
+        #[inline]
+        pub fn update_tag(self, func: impl FnOnce(usize) -> usize) -> Self {
+            let (ptr, tag) = self.decompose();
+            Self::compose(ptr, func(tag))
+        }
+    }
+
+    doc_comment! {
+        doc_decompose!(),
+        #[inline]
+        pub fn decompose(self) -> (*mut T, usize) {
+            (self.decompose_ptr(), self.decompose_tag())
+        }
+    }
+
+    doc_comment! {
+        doc_decompose_ptr!(),
+        ///
+        /// The metadata of the returned pointer is unchanged.
+        #[inline]
+        pub fn decompose_ptr(self) -> *mut T {
+            self.inner.map_addr(|addr| addr & Self::POINTER_MASK)
+        }
+    }
+
+    doc_comment! {
+        doc_decompose_tag!(),
+        #[inline]
+        pub fn decompose_tag(self) -> usize {
+            crate::decompose_tag(self.inner.addr(), Self::TAG_BITS)
+        }
+    }
+
+    doc_comment! {
+        doc_as_ref!("nullable"),
+        #[inline]
+        pub unsafe fn as_ref<'a>(self) -> Option<&'a T> {
+            self.decompose_ptr().as_ref()
+        }
+    }
+
+    doc_comment! {
+        doc_as_mut!("nullable", FatTagPtr),
+        #[inline]
+        pub unsafe fn as_mut<'a>(self) -> Option<&'a mut T> {
+            self.decompose_ptr().as_mut()
+        }
+    }
+}
+
+/********** impl Debug ****************************************************************************/
+
+impl<T: ?Sized, const N: usize> fmt::Debug for FatTagPtr<T, N> {
+    impl_debug!("FatTagPtr");
+}
+
+/********** impl From (&T) ************************************************************************/
+
+impl<T: ?Sized, const N: usize> From<&T> for FatTagPtr<T, N> {
+    #[inline]
+    fn from(reference: &T) -> Self {
+        Self::new(reference as *const T as *mut T)
+    }
+}
+
+/********** impl From (&mut T) ********************************************************************/
+
+impl<T: ?Sized, const N: usize> From<&mut T> for FatTagPtr<T, N> {
+    #[inline]
+    fn from(reference: &mut T) -> Self {
+        Self::new(reference)
+    }
+}
+
+/********** impl PartialEq ************************************************************************/
+
+impl<T: ?Sized, const N: usize> PartialEq for FatTagPtr<T, N> {
+    /// Compares both the (marked) data pointers and the metadata.
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.inner, other.inner)
+    }
+}
+
+/********** impl Eq *******************************************************************************/
+
+impl<T: ?Sized, const N: usize> Eq for FatTagPtr<T, N> {}
+
+/********** impl Hash *****************************************************************************/
+
+impl<T: ?Sized, const N: usize> Hash for FatTagPtr<T, N> {
+    impl_hash!();
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{fmt::Debug, ptr};
+
+    #[repr(align(4))]
+    struct Aligned([u8; 4]);
+
+    #[test]
+    fn test_slice() {
+        type FatTagPtr = crate::FatTagPtr<[u8], 2>;
+
+        let mut array = Aligned([1, 2, 3, 4]);
+        let slice: &mut [u8] = &mut array.0;
+        let raw = slice as *mut [u8];
+
+        let ptr = FatTagPtr::compose(raw, 0b11);
+        assert_eq!(ptr.decompose_tag(), 0b11);
+        assert_eq!(ptr.into_raw().len(), 4);
+        assert!(ptr::eq(ptr.decompose_ptr(), raw));
+        assert_eq!(ptr.set_tag(0b110).decompose_tag(), 0b10);
+        assert_eq!(ptr.clear_tag(), FatTagPtr::new(raw));
+
+        unsafe {
+            ptr.as_mut().unwrap()[3] = 5;
+            assert_eq!(ptr.as_ref(), Some(&[1, 2, 3, 5][..]));
+        }
+    }
+
+    #[test]
+    fn test_trait_object() {
+        type FatTagPtr<'a> = crate::FatTagPtr<dyn Debug + 'a, 2>;
+
+        let value = 1u32;
+        let ptr = FatTagPtr::from(&value as &dyn Debug).set_tag(0b01);
+
+        let (reference, tag) = (unsafe { ptr.as_ref() }.unwrap(), ptr.decompose_tag());
+        assert_eq!(std::format!("{:?}", reference), "1");
+        assert_eq!(tag, 0b01);
+    }
+}
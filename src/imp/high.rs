@@ -0,0 +1,247 @@
+use core::{
+    cmp, fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ptr,
+};
+
+use crate::HighTagPtr;
+
+/********** impl Clone ****************************************************************************/
+
+impl<T, const N: usize> Clone for HighTagPtr<T, N> {
+    impl_clone!();
+}
+
+/********** impl Copy *****************************************************************************/
+
+impl<T, const N: usize> Copy for HighTagPtr<T, N> {}
+
+/********** impl inherent *************************************************************************/
+
+impl<T, const N: usize> HighTagPtr<T, N> {
+    doc_comment! {
+        doc_tag_bits!(),
+        pub const TAG_BITS: usize = N;
+    }
+
+    /// The bitmask for the upper bits of the pointer used for storing the tag
+    /// value.
+    pub const TAG_MASK: usize = crate::mark_mask(Self::TAG_BITS).rotate_right(N as u32);
+
+    /// The bitmask for the lower bits of the pointer used for storing the
+    /// pointer value.
+    pub const POINTER_MASK: usize = !Self::TAG_MASK;
+
+    /// Fails to compile (when evaluated) if `N` leaves no bits of the pointer
+    /// for storing the address.
+    const ASSERT_TAG_BITS: () =
+        assert!(N < usize::BITS as usize, "`N` must be less than the number of pointer bits");
+
+    doc_comment! {
+        doc_null!(),
+        #[inline]
+        pub const fn null() -> Self {
+            Self::new(ptr::null_mut())
+        }
+    }
+
+    doc_comment! {
+        doc_new!(),
+        #[inline]
+        pub const fn new(ptr: *mut T) -> Self {
+            let () = Self::ASSERT_TAG_BITS;
+            Self { inner: ptr, _marker: PhantomData }
+        }
+    }
+
+    /// Composes a new marked pointer from a raw `ptr` and a `tag` value.
+    ///
+    /// The supplied `ptr` is assumed to be in canonical form (i.e., its upper
+    /// `N + 1` bits are either all zero or all one) and any bits of `tag`
+    /// exceeding the available tag bits are silently truncated.
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only* if `ptr` is not in canonical form.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(target_pointer_width = "64")] {
+    /// type HighTagPtr = tagptr::HighTagPtr<i32, 16>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = HighTagPtr::compose(reference, 0xcafe);
+    /// assert_eq!(ptr.decompose(), (reference as *mut _, 0xcafe));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn compose(ptr: *mut T, tag: usize) -> Self {
+        debug_check_eq!(Self::canonical(ptr.addr()), ptr.addr(), "raw pointer must be canonical");
+        let tag = (tag & crate::mark_mask(N)).rotate_right(N as u32);
+        Self::new(ptr.map_addr(|addr| (addr & Self::POINTER_MASK) | tag))
+    }
+
+    doc_comment! {
+        doc_into_raw!(),
+        #[inline]
+        pub const fn into_raw(self) -> *mut T {
+            self.inner
+        }
+    }
+
+    /// Returns `true` if the marked pointer is `null` (regardless of its tag).
+    #[inline]
+    pub fn is_null(self) -> bool {
+        self.decompose_ptr().is_null()
+    }
+
+    doc_comment! {
+        doc_clear_tag!(),
+        #[inline]
+        pub fn clear_tag(self) -> Self {
+            Self::new(self.decompose_ptr())
+        }
+    }
+
+    doc_comment! {
+        doc_split_tag!(),
+        #[inline]
+        pub fn split_tag(self) -> (Self, usize) {
+            let (ptr, tag) = self.decompose();
+            (Self::new(ptr), tag)
+        }
+    }
+
+    doc_comment! {
+        doc_set_tag!(),
+        #[inline]
+        pub fn set_tag(self, tag: usize) -> Self {
+            Self::compose(self.decompose_ptr(), tag)
+        }
+    }
+
+    doc_comment! {
+        doc_update_tag!(),
+        #[inline]
+        pub fn update_tag(self, func: impl FnOnce(usize) -> usize) -> Self {
+            let (ptr, tag) = self.decompose();
+            Self::compose(ptr, func(tag))
+        }
+    }
+
+    doc_comment! {
+        doc_decompose!(),
+        #[inline]
+        pub fn decompose(self) -> (*mut T, usize) {
+            (self.decompose_ptr(), self.decompose_tag())
+        }
+    }
+
+    doc_comment! {
+        doc_decompose_ptr!(),
+        ///
+        /// The returned pointer is in canonical form, i.e., the tag bits are
+        /// replaced by the sign-extension of the highest pointer bit.
+        #[inline]
+        pub fn decompose_ptr(self) -> *mut T {
+            self.inner.map_addr(Self::canonical)
+        }
+    }
+
+    doc_comment! {
+        doc_decompose_tag!(),
+        #[inline]
+        pub fn decompose_tag(self) -> usize {
+            self.inner.addr().rotate_left(N as u32) & crate::mark_mask(N)
+        }
+    }
+
+    /// Returns the canonical form of `addr` by sign-extending its highest
+    /// pointer bit into the tag bits.
+    #[inline]
+    const fn canonical(addr: usize) -> usize {
+        ((addr << N) as isize >> N) as usize
+    }
+}
+
+/********** impl Debug ****************************************************************************/
+
+impl<T, const N: usize> fmt::Debug for HighTagPtr<T, N> {
+    impl_debug!("HighTagPtr");
+}
+
+/********** impl Default **************************************************************************/
+
+impl<T, const N: usize> Default for HighTagPtr<T, N> {
+    impl_default!();
+}
+
+/********** impl PartialEq ************************************************************************/
+
+impl<T, const N: usize> PartialEq for HighTagPtr<T, N> {
+    impl_partial_eq!();
+}
+
+/********** impl PartialOrd ***********************************************************************/
+
+impl<T, const N: usize> PartialOrd for HighTagPtr<T, N> {
+    impl_partial_ord!();
+}
+
+/********** impl Eq *******************************************************************************/
+
+impl<T, const N: usize> Eq for HighTagPtr<T, N> {}
+
+/********** impl Ord ******************************************************************************/
+
+impl<T, const N: usize> Ord for HighTagPtr<T, N> {
+    impl_ord!();
+}
+
+/********** impl Hash *****************************************************************************/
+
+impl<T, const N: usize> Hash for HighTagPtr<T, N> {
+    impl_hash!();
+}
+
+#[cfg(all(test, target_pointer_width = "64"))]
+mod tests {
+    use core::ptr;
+
+    type HighTagPtr = crate::HighTagPtr<i32, 16>;
+
+    #[test]
+    fn test_masks() {
+        assert_eq!(HighTagPtr::TAG_MASK, 0xffff_0000_0000_0000);
+        assert_eq!(HighTagPtr::POINTER_MASK, 0x0000_ffff_ffff_ffff);
+        assert_eq!(crate::HighTagPtr::<i32, 0>::TAG_MASK, 0);
+    }
+
+    #[test]
+    fn test_compose_and_decompose() {
+        let reference = &mut 1;
+        let ptr = HighTagPtr::compose(reference, 0xbeef);
+
+        assert_eq!(ptr.decompose(), (reference as *mut _, 0xbeef));
+        assert_eq!(ptr.into_raw().addr() >> 48, 0xbeef);
+        assert_eq!(ptr.set_tag(0x1_0001).decompose(), (reference as *mut _, 0x0001));
+        assert_eq!(ptr.update_tag(|tag| tag + 0x4111).decompose_tag(), 0);
+        assert_eq!(ptr.split_tag(), (HighTagPtr::new(reference), 0xbeef));
+
+        unsafe { *ptr.decompose_ptr() = 2 };
+        assert_eq!(*reference, 2);
+    }
+
+    #[test]
+    fn test_sign_extension() {
+        let addr = 0xffff_8000_0000_1000;
+        let ptr = HighTagPtr::compose(ptr::without_provenance_mut(addr), 0x1234);
+
+        assert_eq!(ptr.into_raw().addr(), 0x1234_8000_0000_1000);
+        assert_eq!(ptr.decompose_ptr().addr(), addr);
+        assert_eq!(ptr.decompose_tag(), 0x1234);
+        assert!(HighTagPtr::null().set_tag(0xffff).is_null());
+    }
+}
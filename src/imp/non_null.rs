@@ -5,10 +5,13 @@ use core::{
     hash::{Hash, Hasher},
     marker::PhantomData,
     mem,
-    ptr::NonNull,
+    ptr::{self, NonNull},
 };
 
-use crate::{Null, TagNonNull, TagPtr};
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+use crate::{DecomposedNonNull, Null, TagNonNull, TagPtr};
 
 /********** impl Clone ****************************************************************************/
 
@@ -56,12 +59,46 @@ impl<T, const N: usize> TagNonNull<T, N> {
 
     doc_comment! {
         doc_from_usize!(),
+        ///
+        /// The resulting pointer has the provenance of any previously exposed
+        /// pointer with the same address (see
+        /// [`with_exposed_provenance_mut`][ptr::with_exposed_provenance_mut]).
+        ///
+        /// # Safety
+        ///
+        /// The caller has to ensure that `val` represents neither a marked nor
+        /// an unmarked `null` pointer.
         #[inline]
         pub const unsafe fn from_usize(val: usize) -> Self {
-            Self { inner: NonNull::new_unchecked(val as *mut _), _marker: PhantomData }
+            let ptr = ptr::with_exposed_provenance_mut(val);
+            Self { inner: NonNull::new_unchecked(ptr), _marker: PhantomData }
         }
     }
 
+    /// Creates a new pointer from the numeric (integer) representation of a
+    /// potentially marked pointer, if its pointer bits are non-zero.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `val` represents a (marked or unmarked) `null` pointer, in
+    /// which case a [`Null`] instance is returned containing the tag value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tagptr::Null;
+    ///
+    /// type TagNonNull = tagptr::TagNonNull<i32, 2>;
+    ///
+    /// assert_eq!(TagNonNull::try_from_usize(0b10), Err(Null(0b10)));
+    /// let ptr = TagNonNull::try_from_usize(0x1000 | 0b10).unwrap();
+    /// assert_eq!(ptr.into_usize(), 0x1000 | 0b10);
+    /// ```
+    #[inline]
+    pub fn try_from_usize(val: usize) -> Result<Self, Null> {
+        Self::try_from(TagPtr::from_usize(val))
+    }
+
     doc_comment! {
         doc_into_raw!(),
         #[inline]
@@ -77,11 +114,52 @@ impl<T, const N: usize> TagNonNull<T, N> {
         }
     }
 
+    /// Reinterprets the marked pointer's (unchanged) numeric representation
+    /// with `M` instead of `N` tag bits.
+    ///
+    /// If `M` is less than or equal to `N`, the pointer bits are a superset of
+    /// the previous pointer bits and hence remain non-null.
+    /// Otherwise, the pointer bits remain non-null as long as the address has
+    /// any bits set above the lowest `M` bits, which is always the case for
+    /// valid pointers to types with an alignment of at least `2^M`.
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only*, if the pointer bits are zero when
+    /// considering `M` tag bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::ptr::NonNull;
+    ///
+    /// type TagNonNull = tagptr::TagNonNull<u64, 3>;
+    ///
+    /// let reference = &mut 1;
+    /// let raw = NonNull::from(reference);
+    /// let ptr = TagNonNull::compose(raw, 0b110);
+    ///
+    /// let ptr = ptr.with_bits::<1>();
+    /// assert_eq!(ptr.decompose_tag(), 0b0);
+    /// assert_eq!(ptr.decompose_ptr() as usize, raw.as_ptr() as usize | 0b110);
+    /// ```
+    #[inline]
+    pub fn with_bits<const M: usize>(self) -> TagNonNull<T, M> {
+        debug_check!(
+            self.into_usize() & !crate::mark_mask(M) != 0,
+            "pointer bits must be non-zero for the new number of tag bits"
+        );
+        TagNonNull { inner: self.inner, _marker: PhantomData }
+    }
+
     doc_comment! {
         doc_into_usize!(),
+        ///
+        /// This exposes the provenance of the pointer, so it can later be
+        /// recovered through [`from_usize`][TagNonNull::from_usize].
         #[inline]
         pub fn into_usize(self) -> usize {
-            self.inner.as_ptr() as _
+            self.inner.as_ptr().expose_provenance()
         }
     }
 
@@ -91,6 +169,30 @@ impl<T, const N: usize> TagNonNull<T, N> {
         TagPtr::new(self.inner.as_ptr())
     }
 
+    /// Converts `self` into a raw [`NonNull`] pointer, discarding the tag
+    /// value.
+    ///
+    /// This is equivalent to
+    /// [`decompose_non_null`][TagNonNull::decompose_non_null].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::ptr::NonNull;
+    ///
+    /// type TagNonNull = tagptr::TagNonNull<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagNonNull::compose(NonNull::from(reference), 0b11);
+    ///
+    /// let raw = ptr.into_non_null();
+    /// assert_eq!(unsafe { *raw.as_ref() }, 1);
+    /// ```
+    #[inline]
+    pub fn into_non_null(self) -> NonNull<T> {
+        self.decompose_non_null()
+    }
+
     /// Creates a new non-null pointer from `marked_ptr`.
     ///
     /// # Errors
@@ -106,9 +208,10 @@ impl<T, const N: usize> TagNonNull<T, N> {
     #[inline]
     pub const fn dangling() -> Self {
         let alignment = mem::align_of::<T>();
-        let val = if alignment >= Self::TAG_MASK + 1 { alignment } else { Self::TAG_MASK + 1 };
+        let val = if alignment > Self::TAG_MASK { alignment } else { Self::TAG_MASK + 1 };
         // SAFETY: a type's alignment is never 0, so val is always non-zero
-        unsafe { Self::from_usize(val) }
+        let inner = unsafe { NonNull::new_unchecked(ptr::without_provenance_mut(val)) };
+        Self { inner, _marker: PhantomData }
     }
 
     doc_comment! {
@@ -151,6 +254,70 @@ impl<T, const N: usize> TagNonNull<T, N> {
         Self::new_unchecked(TagPtr::compose(ptr.as_ptr(), tag))
     }
 
+    /// Composes a new marked pointer from a raw `ptr` and a `tag` value
+    /// without checking if `ptr` is `null`.
+    ///
+    /// # Safety
+    ///
+    /// The caller has to ensure that `ptr` is non-null even after considering
+    /// its `N` lower bits as tag bits.
+    #[inline]
+    pub unsafe fn new_unchecked_compose(ptr: *mut T, tag: usize) -> Self {
+        Self::new_unchecked(TagPtr::compose(ptr, tag))
+    }
+
+    /// Leaks the given `boxed` value and composes the resulting (non-null)
+    /// pointer with `tag`.
+    ///
+    /// The allocation can only be reclaimed by calling
+    /// [`into_box`][TagNonNull::into_box] on the returned pointer (or any copy
+    /// of it) exactly once.
+    /// Otherwise, the boxed value is leaked and its destructor never runs.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`compose`][TagNonNull::compose],
+    /// which is only possible for zero-sized types with insufficient
+    /// alignment for `N` tag bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagNonNull = tagptr::TagNonNull<i32, 2>;
+    ///
+    /// let ptr = TagNonNull::from_box(Box::new(1), 0b11);
+    /// assert_eq!(ptr.decompose_tag(), 0b11);
+    ///
+    /// let boxed = unsafe { ptr.into_box() };
+    /// assert_eq!(*boxed, 1);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn from_box(boxed: Box<T>, tag: usize) -> Self {
+        Self::compose(NonNull::from(Box::leak(boxed)), tag)
+    }
+
+    /// Reclaims the [`Box`] this marked pointer was created from, discarding
+    /// the tag value.
+    ///
+    /// The tag bits are always stripped before the pointer is handed back to
+    /// the allocator.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must have been created by
+    /// [`from_box`][TagNonNull::from_box] (or point to memory allocated by
+    /// [`Box`] in some other way) and the allocation must not have been
+    /// reclaimed before.
+    /// Calling this method more than once for the same allocation or any
+    /// further access through other copies of the pointer results in a
+    /// double-free or use-after-free.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub unsafe fn into_box(self) -> Box<T> {
+        Box::from_raw(self.decompose_ptr())
+    }
+
     doc_comment! {
         doc_clear_tag!(),
         #[inline]
@@ -172,9 +339,8 @@ impl<T, const N: usize> TagNonNull<T, N> {
         doc_set_tag!(),
         #[inline]
         pub fn set_tag(self, tag: usize) -> Self {
-            let ptr = self.decompose_non_null();
-            // SAFETY: ptr was decomposed from a valid marked non-nullable pointer
-            unsafe { Self::compose_unchecked(ptr, tag) }
+            // SAFETY: setting the tag leaves the (non-null) pointer bits unchanged
+            unsafe { Self::new_unchecked(self.into_marked_ptr().set_tag(tag)) }
         }
     }
 
@@ -182,9 +348,8 @@ impl<T, const N: usize> TagNonNull<T, N> {
         doc_update_tag!(),
         #[inline]
         pub fn update_tag(self, func: impl FnOnce(usize) -> usize) -> Self {
-            let (ptr, tag) = self.decompose();
-            // SAFETY: ptr was decomposed from a valid marked non-nullable pointer
-            unsafe { Self::compose_unchecked(ptr, func(tag)) }
+            // SAFETY: updating the tag leaves the (non-null) pointer bits unchanged
+            unsafe { Self::new_unchecked(self.into_marked_ptr().update_tag(func)) }
         }
     }
 
@@ -194,9 +359,12 @@ impl<T, const N: usize> TagNonNull<T, N> {
         ///
         /// The caller has to ensure that the resulting pointer is not
         /// `null` (neither marked nor unmarked).
+        /// An overflow into the pointer bits changes the pointer's address,
+        /// but can only make it `null` if it points to the very end of the
+        /// address space.
         #[inline]
         pub unsafe fn add_tag(self, value: usize) -> Self {
-            Self::from_usize(self.into_usize().wrapping_add(value))
+            Self::new_unchecked(self.into_marked_ptr().add_tag(value))
         }
     }
 
@@ -206,9 +374,12 @@ impl<T, const N: usize> TagNonNull<T, N> {
         ///
         /// The caller has to ensure that the resulting pointer is not
         /// `null` (neither marked nor unmarked).
+        /// An underflow into the pointer bits changes the pointer's address,
+        /// but can only make it `null` if it points to the very start of the
+        /// address space.
         #[inline]
         pub unsafe fn sub_tag(self, value: usize) -> Self {
-            Self::from_usize(self.into_usize().wrapping_sub(value))
+            Self::new_unchecked(self.into_marked_ptr().sub_tag(value))
         }
     }
 
@@ -220,11 +391,31 @@ impl<T, const N: usize> TagNonNull<T, N> {
         }
     }
 
+    /// Decomposes the marked pointer, returning the non-null pointer and the
+    /// separated tag value as named fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::ptr::NonNull;
+    ///
+    /// type TagNonNull = tagptr::TagNonNull<i32, 2>;
+    ///
+    /// let reference = NonNull::from(&mut 1);
+    /// let parts = TagNonNull::compose(reference, 0b10).decompose_parts();
+    /// assert_eq!(parts.ptr, reference);
+    /// assert_eq!(parts.tag, 0b10);
+    /// ```
+    #[inline]
+    pub fn decompose_parts(self) -> DecomposedNonNull<T> {
+        DecomposedNonNull { ptr: self.decompose_non_null(), tag: self.decompose_tag() }
+    }
+
     doc_comment! {
         doc_decompose_ptr!(),
         #[inline]
         pub fn decompose_ptr(self) -> *mut T {
-            crate::decompose_ptr(self.inner.as_ptr() as usize, Self::TAG_BITS)
+            crate::decompose_ptr(self.inner.as_ptr(), Self::TAG_BITS)
         }
     }
 
@@ -241,14 +432,60 @@ impl<T, const N: usize> TagNonNull<T, N> {
         doc_decompose_tag!(),
         #[inline]
         pub fn decompose_tag(self) -> usize {
-            crate::decompose_tag::<T>(self.inner.as_ptr() as usize, Self::TAG_BITS)
+            crate::decompose_tag(self.inner.as_ptr().addr(), Self::TAG_BITS)
         }
     }
 
+    /// Returns `true` if both marked pointers have the same pointer bits,
+    /// regardless of their tag values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::ptr::NonNull;
+    ///
+    /// type TagNonNull = tagptr::TagNonNull<i32, 2>;
+    ///
+    /// let reference = NonNull::from(&mut 1);
+    /// let (a, b) = (TagNonNull::compose(reference, 0b01), TagNonNull::compose(reference, 0b10));
+    ///
+    /// assert_ne!(a, b);
+    /// assert!(a.ptr_eq(b));
+    /// ```
+    #[inline]
+    pub fn ptr_eq(self, other: Self) -> bool {
+        self.decompose_non_null() == other.decompose_non_null()
+    }
+
+    /// Returns `true` if both marked pointers have the same tag value,
+    /// regardless of their pointer bits.
+    #[inline]
+    pub fn tag_eq(self, other: Self) -> bool {
+        self.decompose_tag() == other.decompose_tag()
+    }
+
+    /// Returns `true` if any of the marked pointer's tag bits are set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::ptr::NonNull;
+    ///
+    /// type TagNonNull = tagptr::TagNonNull<i32, 2>;
+    ///
+    /// let reference = NonNull::from(&mut 1);
+    /// assert!(!TagNonNull::compose(reference, 0b00).is_tagged());
+    /// assert!(TagNonNull::compose(reference, 0b01).is_tagged());
+    /// ```
+    #[inline]
+    pub fn is_tagged(self) -> bool {
+        self.decompose_tag() != 0
+    }
+
     doc_comment! {
         doc_as_ref!("non-nullable"),
         #[inline]
-        pub unsafe fn as_ref(&self) -> &T {
+        pub unsafe fn as_ref<'a>(&self) -> &'a T {
             &*self.decompose_non_null().as_ptr()
         }
     }
@@ -256,7 +493,7 @@ impl<T, const N: usize> TagNonNull<T, N> {
     doc_comment! {
         doc_as_mut!("non-nullable", TagNonNull),
         #[inline]
-        pub unsafe fn as_mut(&mut self) -> &mut T {
+        pub unsafe fn as_mut<'a>(&mut self) -> &'a mut T {
             &mut *self.decompose_non_null().as_ptr()
         }
     }
@@ -268,7 +505,7 @@ impl<T, const N: usize> TagNonNull<T, N> {
     ///
     /// The same safety caveats as with [`as_ref`][TagNonNull::as_ref] apply.
     #[inline]
-    pub unsafe fn decompose_ref(&self) -> (&T, usize) {
+    pub unsafe fn decompose_ref<'a>(&self) -> (&'a T, usize) {
         let (ptr, tag) = self.decompose();
         (&*ptr.as_ptr(), tag)
     }
@@ -280,7 +517,7 @@ impl<T, const N: usize> TagNonNull<T, N> {
     ///
     /// The same safety caveats as with [`as_mut`][TagNonNull::as_mut] apply.
     #[inline]
-    pub unsafe fn decompose_mut(&mut self) -> (&mut T, usize) {
+    pub unsafe fn decompose_mut<'a>(&mut self) -> (&'a mut T, usize) {
         let (ptr, tag) = self.decompose();
         (&mut *ptr.as_ptr(), tag)
     }
@@ -301,6 +538,14 @@ impl<T, const N: usize> fmt::Debug for TagNonNull<T, N> {
     impl_debug!("TagNonNull");
 }
 
+/********** impl Display **************************************************************************/
+
+/// Formats the marked pointer as its (tag-stripped) address in hexadecimal
+/// and its tag value in binary, padded to `N` digits.
+impl<T, const N: usize> fmt::Display for TagNonNull<T, N> {
+    impl_display!();
+}
+
 /********** impl Pointer **************************************************************************/
 
 impl<T, const N: usize> fmt::Pointer for TagNonNull<T, N> {
@@ -360,7 +605,7 @@ impl<T, const N: usize> TryFrom<*mut T> for TagNonNull<T, N> {
 
     #[inline]
     fn try_from(ptr: *mut T) -> Result<Self, Self::Error> {
-        Self::try_compose_inner(ptr, 0)
+        Self::try_from(TagPtr::new(ptr))
     }
 }
 
@@ -375,14 +620,42 @@ impl<T, const N: usize> TryFrom<*const T> for TagNonNull<T, N> {
     }
 }
 
-/********** impl TryFrom (TagPtr) **************************************************************/
+/********** impl TryFrom (TagPtr) *****************************************************************/
 
 impl<T, const N: usize> TryFrom<TagPtr<T, N>> for TagNonNull<T, N> {
     type Error = Null;
 
+    /// Converts a [`TagPtr`] into a [`TagNonNull`], preserving its tag.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `ptr` is a (marked or unmarked) `null` pointer, in which case
+    /// a [`Null`] instance is returned containing the tag value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::{convert::TryFrom, ptr};
+    ///
+    /// use tagptr::Null;
+    ///
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    /// type TagNonNull = tagptr::TagNonNull<i32, 2>;
+    ///
+    /// let res = TagNonNull::try_from(TagPtr::compose(ptr::null_mut(), 0b11));
+    /// assert_eq!(res, Err(Null(0b11)));
+    ///
+    /// let mut val = 1;
+    /// let ptr = TagNonNull::try_from(TagPtr::compose(&mut val, 0b01)).unwrap();
+    /// assert_eq!(ptr.decompose_tag(), 0b01);
+    /// ```
     #[inline]
     fn try_from(ptr: TagPtr<T, N>) -> Result<Self, Self::Error> {
-        Self::try_from(ptr.into_raw())
+        match ptr.decompose() {
+            (raw, tag) if raw.is_null() => Err(Null(tag)),
+            // SAFETY: the pointer's upper bits are non-zero
+            _ => Ok(unsafe { Self::new_unchecked(ptr) }),
+        }
     }
 }
 
@@ -397,13 +670,48 @@ impl<T, const N: usize> TryFrom<NonNull<T>> for TagNonNull<T, N> {
     }
 }
 
+/********** impl Clone (DecomposedNonNull) ********************************************************/
+
+impl<T> Clone for DecomposedNonNull<T> {
+    impl_clone!();
+}
+
+/********** impl Copy (DecomposedNonNull) *********************************************************/
+
+impl<T> Copy for DecomposedNonNull<T> {}
+
+/********** impl Debug (DecomposedNonNull) ********************************************************/
+
+impl<T> fmt::Debug for DecomposedNonNull<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DecomposedNonNull").field("ptr", &self.ptr).field("tag", &self.tag).finish()
+    }
+}
+
+/********** impl PartialEq (DecomposedNonNull) ****************************************************/
+
+impl<T> PartialEq for DecomposedNonNull<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr && self.tag == other.tag
+    }
+}
+
+/********** impl Eq (DecomposedNonNull) ***********************************************************/
+
+impl<T> Eq for DecomposedNonNull<T> {}
+
 #[cfg(test)]
 mod tests {
-    use core::ptr::NonNull;
+    use core::ptr::{self, NonNull};
+
+    use core::convert::TryFrom;
 
     use crate::Null;
 
     type TagNonNull = crate::TagNonNull<i32, 2>;
+    type TagPtr = crate::TagPtr<i32, 2>;
 
     #[test]
     fn test_dangling() {
@@ -414,6 +722,144 @@ mod tests {
         assert_eq!(crate::TagNonNull::<Alignment64, 0>::dangling().into_usize(), 64);
     }
 
+    #[test]
+    fn test_new() {
+        assert_eq!(TagNonNull::new(TagPtr::null()), Err(Null(0)));
+        assert_eq!(TagNonNull::new(TagPtr::compose(ptr::null_mut(), 0b10)), Err(Null(0b10)));
+
+        let value = &mut 1;
+        let reference = NonNull::from(value);
+        let ptr = TagNonNull::new(TagPtr::compose(reference.as_ptr(), 0b01)).unwrap();
+        assert_eq!(ptr.decompose(), (reference, 0b01));
+
+        let ptr = unsafe { TagNonNull::new_unchecked(TagPtr::compose(reference.as_ptr(), 0b10)) };
+        assert_eq!(ptr.decompose(), (reference, 0b10));
+        assert_eq!(TagNonNull::dangling().decompose_tag(), 0);
+    }
+
+    #[test]
+    fn test_decompose() {
+        let reference = &mut 1;
+        let raw = NonNull::from(reference);
+        let ptr = TagNonNull::compose(raw, 0b11);
+
+        assert_eq!(ptr.decompose(), (raw, 0b11));
+        assert_eq!(ptr.decompose_non_null(), raw);
+        assert_eq!(ptr.decompose_ptr(), raw.as_ptr());
+        assert_eq!(ptr.decompose_tag(), 0b11);
+        assert_eq!(unsafe { *ptr.decompose_non_null().as_ref() }, 1);
+    }
+
+    #[test]
+    fn test_set_tag() {
+        let reference = NonNull::from(&mut 1);
+        let ptr = TagNonNull::compose(reference, 0b11);
+        // set_tag must silently truncate excess tag bits
+        assert_eq!(ptr, ptr.set_tag(0b111));
+        assert_eq!(ptr.set_tag(0b01).decompose(), (reference, 0b01));
+        assert_eq!(ptr.clear_tag(), TagNonNull::compose(reference, 0));
+        assert_eq!(ptr.split_tag(), (TagNonNull::compose(reference, 0), 0b11));
+    }
+
+    #[test]
+    fn test_update_tag() {
+        let reference = NonNull::from(&mut 1);
+        let ptr = TagNonNull::compose(reference, 0b10);
+
+        // update_tag must only overflow the tag bits
+        assert_eq!(ptr.update_tag(|tag| tag + 1).decompose(), (reference, 0b11));
+        assert_eq!(ptr.update_tag(|tag| tag + 0b10).decompose(), (reference, 0));
+    }
+
+    #[test]
+    fn test_overflow_tag() {
+        let reference = NonNull::from(&mut 1);
+        let ptr = TagNonNull::compose(reference, 0b11);
+
+        // add must cause overflow (corrupt the pointer)
+        let res = unsafe { ptr.add_tag(1) };
+        assert_eq!(res.into_usize(), reference.as_ptr() as usize + 0b11 + 1);
+        assert_eq!(unsafe { res.sub_tag(1) }, ptr);
+    }
+
+    #[test]
+    fn test_underflow_tag() {
+        let reference = NonNull::from(&mut 1);
+        let ptr = TagNonNull::compose(reference, 0);
+
+        // sub_tag must underflow the entire pointer
+        let res = unsafe { ptr.sub_tag(1) };
+        assert_eq!(res.into_usize(), reference.as_ptr() as usize - 1);
+        assert_eq!(res.decompose_tag(), 0b11);
+    }
+
+    #[test]
+    fn test_ptr_eq_and_tag_eq() {
+        let (a, b) = (NonNull::from(&mut 1), NonNull::from(&mut 2));
+
+        let ptr = TagNonNull::compose(a, 0b01);
+        assert!(ptr.ptr_eq(ptr.set_tag(0b10)));
+        assert!(!ptr.tag_eq(ptr.set_tag(0b10)));
+        assert!(!ptr.ptr_eq(TagNonNull::compose(b, 0b01)));
+        assert!(ptr.tag_eq(TagNonNull::compose(b, 0b01)));
+    }
+
+    #[test]
+    fn test_decompose_parts() {
+        let reference = NonNull::from(&mut 1);
+        let ptr = TagNonNull::compose(reference, 0b01);
+
+        let parts = ptr.decompose_parts();
+        assert_eq!((parts.ptr, parts.tag), ptr.decompose());
+        assert_eq!(parts, ptr.set_tag(0b01).decompose_parts());
+        assert_ne!(parts, ptr.clear_tag().decompose_parts());
+    }
+
+    #[test]
+    fn test_display() {
+        let reference = NonNull::from(&mut 1);
+        let ptr = TagNonNull::compose(reference, 0b10);
+        assert_eq!(std::format!("{}", ptr), std::format!("{:p}@0b10", reference));
+    }
+
+    #[test]
+    fn test_as_ref_and_as_mut() {
+        let mut value = 1;
+        let mut ptr = TagNonNull::compose(NonNull::from(&mut value), 0b10);
+
+        unsafe {
+            assert_eq!(ptr.as_ref(), &1);
+            *ptr.as_mut() = 2;
+            assert_eq!(ptr.decompose_ref(), (&2, 0b10));
+
+            let (value, tag) = ptr.decompose_mut();
+            *value += tag as i32;
+        }
+
+        assert_eq!(value, 4);
+    }
+
+    #[test]
+    fn test_compose() {
+        let reference = NonNull::from(&mut 1);
+        // compose must silently truncate excess tag bits
+        let ptr = TagNonNull::compose(reference, 0b101);
+        assert_eq!(ptr.decompose(), (reference, 0b01));
+
+        let ptr = unsafe { TagNonNull::new_unchecked_compose(reference.as_ptr(), 0b110) };
+        assert_eq!(ptr.decompose(), (reference, 0b10));
+    }
+
+    #[test]
+    #[cfg(all(debug_assertions, not(feature = "no-panic")))]
+    #[should_panic(expected = "tag bits in raw pointer must be zeroed")]
+    fn test_compose_misaligned() {
+        // a pointer to a byte buffer element is not aligned for `i32`
+        let mut bytes = [0u8; 8];
+        let misaligned = bytes.as_mut_ptr().map_addr(|addr| addr | 0b1).cast::<i32>();
+        let _ = TagNonNull::compose(NonNull::new(misaligned).unwrap(), 0);
+    }
+
     #[test]
     fn test_try_compose() {
         let reference = &1;
@@ -425,8 +871,103 @@ mod tests {
         let res = TagNonNull::try_compose(dangling, 0).map(|ptr| ptr.decompose());
         assert_eq!(res, Ok((dangling, 0)));
 
-        let ptr = NonNull::new(0b11 as *mut i32).unwrap();
+        let ptr = NonNull::new(ptr::without_provenance_mut::<i32>(0b11)).unwrap();
         let res = TagNonNull::try_compose(ptr, 0b11);
         assert_eq!(res, Err(Null(0b11)));
     }
+
+    #[test]
+    fn test_with_bits() {
+        let reference = &mut 1u64;
+        let raw = NonNull::from(reference);
+        let addr = raw.as_ptr() as usize;
+        let ptr = crate::TagNonNull::<u64, 3>::compose(raw, 0b101);
+
+        let narrow = ptr.with_bits::<1>();
+        assert_eq!(narrow.into_usize(), ptr.into_usize());
+        assert_eq!(narrow.decompose_tag(), 0b1);
+        assert_eq!(narrow.decompose_ptr() as usize, addr | 0b100);
+
+        let none = ptr.with_bits::<0>();
+        assert_eq!(none.decompose(), (ptr.into_raw(), 0));
+
+        let wide = narrow.with_bits::<3>();
+        assert_eq!(wide, ptr);
+        assert_eq!(wide.decompose(), (raw, 0b101));
+    }
+
+    #[test]
+    #[cfg(all(debug_assertions, not(feature = "no-panic")))]
+    #[should_panic(expected = "pointer bits must be non-zero")]
+    fn test_with_bits_null() {
+        let ptr = TagNonNull::try_from_usize(0b100).unwrap();
+        let _ = ptr.with_bits::<3>();
+    }
+
+    #[test]
+    fn test_cast() {
+        let reference = NonNull::from(&mut 1);
+        let ptr = TagNonNull::compose(reference, 0b10);
+
+        let erased: crate::TagNonNull<(), 2> = ptr.cast();
+        assert_eq!(erased.decompose(), (reference.cast(), 0b10));
+        assert_eq!(erased.cast::<i32>(), ptr);
+    }
+
+    #[test]
+    fn test_null_error() {
+        let err = TagNonNull::new(TagPtr::compose(ptr::null_mut(), 0b10)).unwrap_err();
+        #[cfg(feature = "std")]
+        {
+            fn assert_error<E: std::error::Error>(_: &E) {}
+            assert_error(&err);
+        }
+        assert_eq!(std::format!("{}", err), "marked pointer is null (tag: 0b10)");
+    }
+
+    #[test]
+    fn test_try_from_usize() {
+        assert_eq!(TagNonNull::try_from_usize(0), Err(Null(0)));
+        assert_eq!(TagNonNull::try_from_usize(0b11), Err(Null(0b11)));
+
+        let reference = &mut 1;
+        let val = reference as *mut i32 as usize | 0b01;
+        let res = TagNonNull::try_from_usize(val).map(|ptr| ptr.decompose());
+        assert_eq!(res, Ok((NonNull::from(reference), 0b01)));
+    }
+
+    #[test]
+    fn test_try_from_marked() {
+        let reference = &mut 1;
+        let res = TagNonNull::try_from(TagPtr::compose(reference, 0b11)).map(|ptr| ptr.decompose());
+        assert_eq!(res, Ok((NonNull::from(reference), 0b11)));
+
+        let res = TagNonNull::try_from(TagPtr::compose(ptr::null_mut(), 0b10));
+        assert_eq!(res, Err(Null(0b10)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_box_roundtrip() {
+        use core::cell::Cell;
+        use std::boxed::Box;
+
+        struct DropCount<'a>(&'a Cell<usize>);
+
+        impl Drop for DropCount<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let count = Cell::new(0);
+        let ptr = crate::TagNonNull::<_, 2>::from_box(Box::new(DropCount(&count)), 0b11);
+        assert_eq!(ptr.decompose_tag(), 0b11);
+        assert_eq!(count.get(), 0);
+
+        let boxed = unsafe { ptr.into_box() };
+        assert_eq!(count.get(), 0);
+        drop(boxed);
+        assert_eq!(count.get(), 1);
+    }
 }
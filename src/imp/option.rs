@@ -0,0 +1,149 @@
+use core::fmt;
+
+use crate::{Null, TagNonNull, TagOption, TagPtr};
+
+/********** impl Clone ****************************************************************************/
+
+impl<T, const N: usize> Clone for TagOption<T, N> {
+    impl_clone!();
+}
+
+/********** impl Copy *****************************************************************************/
+
+impl<T, const N: usize> Copy for TagOption<T, N> {}
+
+/********** impl inherent *************************************************************************/
+
+impl<T, const N: usize> TagOption<T, N> {
+    /// Converts the (nullable) `marked_ptr` into the respective variant,
+    /// preserving its tag value in either case.
+    #[inline]
+    pub fn from_marked_ptr(marked_ptr: TagPtr<T, N>) -> Self {
+        match TagNonNull::new(marked_ptr) {
+            Ok(ptr) => TagOption::Value(ptr),
+            Err(Null(tag)) => TagOption::Null(tag),
+        }
+    }
+
+    /// Converts `self` back into a (nullable) marked pointer.
+    #[inline]
+    pub fn into_marked_ptr(self) -> TagPtr<T, N> {
+        match self {
+            TagOption::Value(ptr) => ptr.into_marked_ptr(),
+            TagOption::Null(tag) => TagPtr::null().set_tag(tag),
+        }
+    }
+
+    /// Returns `true` if `self` is a [`Value`][TagOption::Value].
+    #[inline]
+    pub fn is_value(self) -> bool {
+        matches!(self, TagOption::Value(_))
+    }
+
+    /// Returns `true` if `self` is a [`Null`][TagOption::Null].
+    #[inline]
+    pub fn is_null(self) -> bool {
+        matches!(self, TagOption::Null(_))
+    }
+
+    /// Returns the tag value of either variant.
+    #[inline]
+    pub fn tag(self) -> usize {
+        match self {
+            TagOption::Value(ptr) => ptr.decompose_tag(),
+            TagOption::Null(tag) => tag,
+        }
+    }
+
+    /// Returns the contained non-null marked pointer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is a [`Null`][TagOption::Null].
+    #[inline]
+    pub fn unwrap_value(self) -> TagNonNull<T, N> {
+        match self {
+            TagOption::Value(ptr) => ptr,
+            TagOption::Null(tag) => {
+                panic!("called `TagOption::unwrap_value()` on a `Null` value (tag: {})", tag)
+            }
+        }
+    }
+}
+
+/********** impl Debug ****************************************************************************/
+
+impl<T, const N: usize> fmt::Debug for TagOption<T, N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TagOption::Value(ptr) => f.debug_tuple("Value").field(ptr).finish(),
+            TagOption::Null(tag) => f.debug_tuple("Null").field(tag).finish(),
+        }
+    }
+}
+
+/********** impl From (TagPtr) ********************************************************************/
+
+impl<T, const N: usize> From<TagPtr<T, N>> for TagOption<T, N> {
+    #[inline]
+    fn from(marked_ptr: TagPtr<T, N>) -> Self {
+        Self::from_marked_ptr(marked_ptr)
+    }
+}
+
+/********** impl PartialEq ************************************************************************/
+
+impl<T, const N: usize> PartialEq for TagOption<T, N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (TagOption::Value(a), TagOption::Value(b)) => a == b,
+            (TagOption::Null(a), TagOption::Null(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/********** impl Eq *******************************************************************************/
+
+impl<T, const N: usize> Eq for TagOption<T, N> {}
+
+#[cfg(test)]
+mod tests {
+    use core::ptr::{self, NonNull};
+
+    type TagNonNull = crate::TagNonNull<i32, 2>;
+    type TagOption = crate::TagOption<i32, 2>;
+    type TagPtr = crate::TagPtr<i32, 2>;
+
+    #[test]
+    fn test_null() {
+        let ptr = TagPtr::compose(ptr::null_mut(), 0b11);
+        let opt = TagOption::from_marked_ptr(ptr);
+
+        assert_eq!(opt, TagOption::Null(0b11));
+        assert!(opt.is_null());
+        assert_eq!(opt.tag(), 0b11);
+        assert_eq!(opt.into_marked_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_value() {
+        let reference = NonNull::from(&mut 1);
+        let ptr = TagPtr::compose(reference.as_ptr(), 0b01);
+        let opt = TagOption::from(ptr);
+
+        assert_eq!(opt, TagOption::Value(TagNonNull::compose(reference, 0b01)));
+        assert!(opt.is_value());
+        assert_eq!(opt.tag(), 0b01);
+        assert_eq!(opt.unwrap_value().decompose(), (reference, 0b01));
+        assert_eq!(opt.into_marked_ptr(), ptr);
+    }
+
+    #[test]
+    #[should_panic(expected = "called `TagOption::unwrap_value()` on a `Null` value")]
+    fn test_unwrap_null() {
+        let _ = TagOption::Null(0b10).unwrap_value();
+    }
+}
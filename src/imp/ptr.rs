@@ -2,10 +2,11 @@ use core::{
     cmp, fmt,
     hash::{Hash, Hasher},
     marker::PhantomData,
+    mem,
     ptr::{self, NonNull},
 };
 
-use crate::{TagNonNull, TagPtr};
+use crate::{ByPtr, ComposeError, DecomposedPtr, LogRepr, TagNonNull, TagPtr};
 
 /********** impl Clone ****************************************************************************/
 
@@ -35,6 +36,13 @@ impl<T, const N: usize> TagPtr<T, N> {
         pub const POINTER_MASK: usize = !Self::TAG_MASK;
     }
 
+    /// Fails to compile (when evaluated) if the alignment of `T` is
+    /// insufficient for storing `N` tag bits.
+    pub(crate) const ASSERT_ALIGNMENT: () = assert!(
+        crate::has_sufficient_alignment::<T>(N),
+        "the alignment of `T` is insufficient for storing `N` tag bits"
+    );
+
     doc_comment! {
         doc_null!(),
         ///
@@ -75,6 +83,10 @@ impl<T, const N: usize> TagPtr<T, N> {
     doc_comment! {
         doc_from_usize!(),
         ///
+        /// The resulting pointer has the provenance of any previously exposed
+        /// pointer with the same address (see
+        /// [`with_exposed_provenance_mut`][ptr::with_exposed_provenance_mut]).
+        ///
         /// # Examples
         ///
         /// ```
@@ -87,7 +99,7 @@ impl<T, const N: usize> TagPtr<T, N> {
         /// ```
         #[inline]
         pub const fn from_usize(val: usize) -> Self {
-            Self::new(val as _)
+            Self::new(ptr::with_exposed_provenance_mut(val))
         }
     }
 
@@ -115,9 +127,73 @@ impl<T, const N: usize> TagPtr<T, N> {
         }
     }
 
+    /// Converts the marked pointer into one with `M` tag bits, truncating the
+    /// tag value to the lower `M` bits.
+    ///
+    /// Any discarded tag bits are cleared, so the pointer bits remain
+    /// unchanged.
+    /// Widening the tag (`M > N`) is rejected at compile-time, since the
+    /// additional tag bits would be taken from the pointer bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<u64, 3>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose(reference, 0b110).convert::<2>();
+    /// assert_eq!(ptr.decompose(), (reference as *mut _, 0b10));
+    /// ```
+    ///
+    /// ```compile_fail
+    /// let ptr = tagptr::TagPtr::<u64, 2>::null().convert::<3>();
+    /// ```
+    #[inline]
+    pub fn convert<const M: usize>(self) -> TagPtr<T, M> {
+        let () = const { assert!(M <= N, "can not widen the tag into the pointer bits") };
+        let (ptr, tag) = self.decompose();
+        TagPtr::new(crate::compose::<T, M>(ptr, tag))
+    }
+
+    /// Converts the marked pointer into one with `M` tag bits, if this is
+    /// possible without discarding any set tag bits.
+    ///
+    /// Like [`convert`][TagPtr::convert], widening the tag (`M > N`) is
+    /// rejected at compile-time.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the tag value exceeds the maximum value representable by `M`
+    /// tag bits, in which case a [`ComposeError::TagOverflow`] containing the
+    /// tag value is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tagptr::ComposeError;
+    ///
+    /// type TagPtr = tagptr::TagPtr<u64, 3>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose(reference, 0b010);
+    /// assert_eq!(ptr.try_convert::<2>().map(|ptr| ptr.decompose_tag()), Ok(0b10));
+    /// let ptr = TagPtr::compose(reference, 0b110);
+    /// assert_eq!(ptr.try_convert::<2>(), Err(ComposeError::TagOverflow(0b110)));
+    /// ```
+    #[inline]
+    pub fn try_convert<const M: usize>(self) -> Result<TagPtr<T, M>, ComposeError> {
+        match self.decompose_tag() {
+            tag if tag > crate::mark_mask(M) => Err(ComposeError::TagOverflow(tag)),
+            _ => Ok(self.convert()),
+        }
+    }
+
     doc_comment! {
         doc_into_usize!(),
         ///
+        /// This exposes the provenance of the pointer, so it can later be
+        /// recovered through [`from_usize`][TagPtr::from_usize].
+        ///
         /// # Examples
         ///
         /// ```
@@ -128,10 +204,36 @@ impl<T, const N: usize> TagPtr<T, N> {
         /// ```
         #[inline]
         pub fn into_usize(self) -> usize {
-            self.inner as usize
+            self.inner.expose_provenance()
         }
     }
 
+    /// Returns the bit representation of the marked pointer for manually
+    /// packing it into a larger word.
+    ///
+    /// This is identical to [`into_usize`][TagPtr::into_usize].
+    /// The bit layout is guaranteed to be `addr | tag`, i.e., the tag value is
+    /// stored in the lowest [`TAG_BITS`][TagPtr::TAG_BITS] bits and the
+    /// (aligned) address occupies all remaining bits, so that the pointer can
+    /// be restored using [`from_usize`][TagPtr::from_usize].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose(reference, 0b10);
+    ///
+    /// let bits = ptr.into_bits();
+    /// assert_eq!(bits, reference as *mut i32 as usize | 0b10);
+    /// assert_eq!(TagPtr::from_usize(bits), ptr);
+    /// ```
+    #[inline]
+    pub fn into_bits(self) -> usize {
+        self.into_usize()
+    }
+
     doc_comment! {
         doc_compose!(),
         ///
@@ -149,10 +251,175 @@ impl<T, const N: usize> TagPtr<T, N> {
         /// ```
         #[inline]
         pub fn compose(ptr: *mut T, tag: usize) -> Self {
+            let () = Self::ASSERT_ALIGNMENT;
             Self::new(crate::compose::<T, N>(ptr, tag))
         }
     }
 
+    /// Attempts to compose a new marked pointer from a raw `ptr` and a `tag`
+    /// value without losing any information.
+    ///
+    /// Unlike [`compose`][TagPtr::compose], this does not require the
+    /// alignment of `T` to be sufficient for `N` tag bits, since the address
+    /// of `ptr` is checked at runtime instead, so it can also be used for
+    /// under-aligned or zero-sized types such as `TagPtr<(), N>`.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`ComposeError::Unaligned`] if any of the `N` lower bits of
+    /// `ptr` are set, which occurs when `T`'s alignment is insufficient for
+    /// `N` tag bits.
+    /// Fails with [`ComposeError::TagOverflow`] if `tag` exceeds
+    /// [`TAG_MASK`][TagPtr::TAG_MASK].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tagptr::ComposeError;
+    ///
+    /// // u16 has an alignment of 2 and hence only one free tag bit
+    /// type TagPtr = tagptr::TagPtr<u16, 3>;
+    ///
+    /// #[repr(align(8))]
+    /// struct Aligned([u16; 4]);
+    ///
+    /// let mut array = Aligned([0; 4]);
+    /// let aligned = array.0.as_mut_ptr();
+    /// let misaligned = aligned.wrapping_add(1);
+    ///
+    /// assert_eq!(TagPtr::try_compose(aligned, 0b11).map(TagPtr::decompose), Ok((aligned, 0b11)));
+    /// assert_eq!(TagPtr::try_compose(aligned, 0b1000), Err(ComposeError::TagOverflow(0b1000)));
+    /// assert_eq!(
+    ///     TagPtr::try_compose(misaligned, 0),
+    ///     Err(ComposeError::Unaligned(misaligned as usize))
+    /// );
+    /// ```
+    #[inline]
+    pub fn try_compose(ptr: *mut T, tag: usize) -> Result<Self, ComposeError> {
+        if ptr.addr() & Self::TAG_MASK != 0 {
+            return Err(ComposeError::Unaligned(ptr.addr()));
+        }
+
+        if tag > Self::TAG_MASK {
+            return Err(ComposeError::TagOverflow(tag));
+        }
+
+        Ok(Self::new(crate::compose::<T, N>(ptr, tag)))
+    }
+
+    /// Composes a new marked pointer from a raw `ptr` and a set of `flags`,
+    /// which are converted into the tag value.
+    ///
+    /// This allows modelling the tag e.g. as a type generated by the
+    /// `bitflags` crate.
+    /// Any bits of the converted flags exceeding
+    /// [`TAG_MASK`][TagPtr::TAG_MASK] are silently truncated and the same
+    /// caveats as for [`compose`][TagPtr::compose] apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Marked(bool);
+    ///
+    /// impl From<Marked> for usize {
+    ///     fn from(marked: Marked) -> usize {
+    ///         marked.0 as usize
+    ///     }
+    /// }
+    ///
+    /// impl From<usize> for Marked {
+    ///     fn from(tag: usize) -> Self {
+    ///         Self(tag & 0b1 == 1)
+    ///     }
+    /// }
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose_flags(reference, Marked(true));
+    /// assert_eq!(ptr.decompose_flags(), (reference as *mut _, Marked(true)));
+    /// ```
+    #[inline]
+    pub fn compose_flags<F: Into<usize>>(ptr: *mut T, flags: F) -> Self {
+        Self::compose(ptr, flags.into())
+    }
+
+    /// Composes a new marked pointer from a raw `ptr` and a set of `flags`
+    /// generated by the `bitflags` crate.
+    ///
+    /// Unlike [`compose_flags`][TagPtr::compose_flags], this does not require
+    /// any conversion impls for the flags type.
+    /// Any bits of the flags exceeding [`TAG_MASK`][TagPtr::TAG_MASK] are
+    /// silently truncated and the same caveats as for
+    /// [`compose`][TagPtr::compose] apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// bitflags::bitflags! {
+    ///     #[derive(Debug, PartialEq)]
+    ///     struct Flags: usize {
+    ///         const MARKED = 0b01;
+    ///         const LOCKED = 0b10;
+    ///     }
+    /// }
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose_bitflags(reference, Flags::MARKED);
+    /// assert_eq!(ptr.decompose_bitflags(), (reference as *mut _, Flags::MARKED));
+    /// ```
+    #[cfg(feature = "bitflags")]
+    #[inline]
+    pub fn compose_bitflags<F: bitflags::Flags<Bits = usize>>(ptr: *mut T, flags: F) -> Self {
+        Self::compose(ptr, flags.bits())
+    }
+
+    /// Composes a new marked pointer from a shared `reference` and a `tag`
+    /// value.
+    ///
+    /// Like [`compose`][TagPtr::compose], the `tag` is truncated to the
+    /// lower `N` bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &1;
+    /// let ptr = TagPtr::from_ref_tagged(reference, 0b10);
+    /// assert_eq!(unsafe { ptr.decompose_ref() }, (Some(&1), 0b10));
+    /// ```
+    #[inline]
+    pub fn from_ref_tagged(reference: &T, tag: usize) -> Self {
+        Self::compose(reference as *const T as *mut T, tag)
+    }
+
+    /// Composes a new marked pointer from a mutable `reference` and a `tag`
+    /// value.
+    ///
+    /// Like [`compose`][TagPtr::compose], the `tag` is truncated to the
+    /// lower `N` bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let mut value = 1;
+    /// let ptr = TagPtr::from_mut_tagged(&mut value, 0b111);
+    /// assert_eq!(ptr.decompose_tag(), 0b11);
+    ///
+    /// unsafe { *ptr.as_mut().unwrap() += 1 };
+    /// assert_eq!(value, 2);
+    /// ```
+    #[inline]
+    pub fn from_mut_tagged(reference: &mut T, tag: usize) -> Self {
+        Self::compose(reference, tag)
+    }
+
     /// Returns `true` if the marked pointer is `null`.
     ///
     /// # Examples
@@ -170,6 +437,379 @@ impl<T, const N: usize> TagPtr<T, N> {
         self.decompose_ptr().is_null()
     }
 
+    /// Converts the marked pointer into a [`TagNonNull`], preserving its tag,
+    /// or returns `None` if it is `null`.
+    ///
+    /// This is equivalent to `TagNonNull::new(self).ok()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::ptr;
+    ///
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose(reference, 0b10).as_non_null().unwrap();
+    /// assert_eq!(ptr.decompose_tag(), 0b10);
+    ///
+    /// // a tagged `null` pointer is still `null`
+    /// assert!(TagPtr::compose(ptr::null_mut(), 0b11).as_non_null().is_none());
+    /// ```
+    #[inline]
+    pub fn as_non_null(self) -> Option<TagNonNull<T, N>> {
+        TagNonNull::new(self).ok()
+    }
+
+    /// Converts the marked pointer into a [`TagNonNull`], preserving its tag,
+    /// without checking if it is `null`.
+    ///
+    /// # Safety
+    ///
+    /// The caller has to ensure that the marked pointer is non-null even after
+    /// considering its `N` lower bits as tag bits.
+    #[inline]
+    pub unsafe fn as_non_null_unchecked(self) -> TagNonNull<T, N> {
+        TagNonNull::new_unchecked(self)
+    }
+
+    /// Returns the maximum tag value representable by `N` tag bits, which is
+    /// equal to [`TAG_MASK`][TagPtr::TAG_MASK].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert_eq!(tagptr::TagPtr::<i32, 2>::max_tag(), 0b11);
+    ///
+    /// // the highest version before the counter wraps around
+    /// const MAX_VERSION: usize = tagptr::TagPtr::<u64, 3>::max_tag();
+    /// assert_eq!(MAX_VERSION, 7);
+    /// ```
+    #[inline]
+    pub const fn max_tag() -> usize {
+        Self::TAG_MASK
+    }
+
+    /// Returns `true` if the tag value is equal to [`TAG_MASK`][TagPtr::TAG_MASK],
+    /// i.e., if the next increment of the tag would wrap around.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// assert!(!TagPtr::compose(reference, 0b10).tag_is_saturated());
+    /// assert!(TagPtr::compose(reference, 0b11).tag_is_saturated());
+    /// ```
+    #[inline]
+    pub fn tag_is_saturated(self) -> bool {
+        self.decompose_tag() == Self::TAG_MASK
+    }
+
+    /// Returns a representation of the marked pointer's address and tag value
+    /// suitable for logging, which can be safely sent to other threads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let ptr = TagPtr::from_usize(0x1000 | 0b11);
+    /// assert_eq!(ptr.log_repr().to_string(), "0x1000@0b11");
+    /// ```
+    #[inline]
+    pub fn log_repr(self) -> LogRepr {
+        let (addr, tag) = self.addr_and_tag();
+        LogRepr { addr, tag }
+    }
+
+    /// Returns `true` if the tag values of `self` and `other` are identical
+    /// except (possibly) for the tag bit at index `bit`.
+    ///
+    /// This can be used, e.g., to verify that some operation changed only a
+    /// single mark bit and nothing else.
+    /// The pointer bits are not considered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose(reference, 0b01);
+    ///
+    /// assert!(ptr.tag_differs_only_in(ptr, 1));
+    /// assert!(ptr.tag_differs_only_in(ptr.set_tag(0b11), 1));
+    /// assert!(!ptr.tag_differs_only_in(ptr.set_tag(0b00), 1));
+    /// ```
+    #[inline]
+    pub fn tag_differs_only_in(self, other: Self, bit: u32) -> bool {
+        debug_check!((bit as usize) < Self::TAG_BITS, "`bit` exceeds tag bits");
+        (self.decompose_tag() ^ other.decompose_tag()) & !(1 << bit) == 0
+    }
+
+    /// Returns `true` if the tag bit at index `bit` is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let ptr = TagPtr::compose(&mut 1, 0b10);
+    /// assert!(!ptr.tag_bit(0));
+    /// assert!(ptr.tag_bit(1));
+    /// ```
+    #[inline]
+    pub fn tag_bit(self, bit: u32) -> bool {
+        debug_check!((bit as usize) < Self::TAG_BITS, "`bit` exceeds tag bits");
+        self.decompose_tag() & (1 << bit) != 0
+    }
+
+    /// Returns the marked pointer with the tag bit at index `bit` set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose(reference, 0b01).set_tag_bit(1);
+    /// assert_eq!(ptr.decompose(), (reference as *mut _, 0b11));
+    /// ```
+    #[inline]
+    pub fn set_tag_bit(self, bit: u32) -> Self {
+        debug_check!((bit as usize) < Self::TAG_BITS, "`bit` exceeds tag bits");
+        self.update_tag(|tag| tag | (1 << bit))
+    }
+
+    /// Returns the marked pointer with the tag bit at index `bit` cleared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose(reference, 0b11).clear_tag_bit(0);
+    /// assert_eq!(ptr.decompose(), (reference as *mut _, 0b10));
+    /// ```
+    #[inline]
+    pub fn clear_tag_bit(self, bit: u32) -> Self {
+        debug_check!((bit as usize) < Self::TAG_BITS, "`bit` exceeds tag bits");
+        self.update_tag(|tag| tag & !(1 << bit))
+    }
+
+    /// Returns the marked pointer with the tag bit at index `bit` inverted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose(reference, 0b01).toggle_tag_bit(0).toggle_tag_bit(1);
+    /// assert_eq!(ptr.decompose(), (reference as *mut _, 0b10));
+    /// ```
+    #[inline]
+    pub fn toggle_tag_bit(self, bit: u32) -> Self {
+        debug_check!((bit as usize) < Self::TAG_BITS, "`bit` exceeds tag bits");
+        self.update_tag(|tag| tag ^ (1 << bit))
+    }
+
+    /// Returns an iterator over the indices of all set tag bits, lowest first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<u64, 3>;
+    ///
+    /// let ptr = TagPtr::compose(&mut 1, 0b110);
+    /// assert!(ptr.tag_bits().eq([1, 2]));
+    /// ```
+    #[inline]
+    pub fn tag_bits(self) -> impl Iterator<Item = u32> {
+        let tag = self.decompose_tag();
+        (0..N as u32).filter(move |&bit| tag & (1 << bit) != 0)
+    }
+
+    /// Returns the index of the shard out of `num_shards` the marked pointer
+    /// is assigned to.
+    ///
+    /// The index is derived from a (multiplicative) hash of only the pointer
+    /// bits, so the same pointer is always assigned to the same shard,
+    /// regardless of its current tag value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_shards` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose(reference, 0b01);
+    ///
+    /// let shard = ptr.shard_index(16);
+    /// assert!(shard < 16);
+    /// assert_eq!(ptr.set_tag(0b10).shard_index(16), shard);
+    /// ```
+    #[inline]
+    pub fn shard_index(self, num_shards: usize) -> usize {
+        const GOLDEN_RATIO: u64 = 0x9e37_79b9_7f4a_7c15;
+        let addr = (self.decompose_ptr().addr() >> Self::TAG_BITS) as u64;
+        let hash = addr.wrapping_mul(GOLDEN_RATIO) >> 32;
+        (hash % num_shards as u64) as usize
+    }
+
+    /// Returns the canonical representation of the marked pointer, i.e., its
+    /// tag-stripped address and its tag value.
+    ///
+    /// This representation is independent of how pointer and tag are laid out
+    /// within the pointer's memory word and is currently identical to
+    /// [`addr_and_tag`][TagPtr::addr_and_tag].
+    #[inline]
+    pub fn canonical(self) -> (usize, usize) {
+        self.addr_and_tag()
+    }
+
+    /// Returns `true` if both marked pointers have the same
+    /// [`canonical`][TagPtr::canonical] representation, i.e., are logically
+    /// composed of the same address and tag value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose(reference, 0b11);
+    ///
+    /// assert!(ptr.canonical_eq(TagPtr::new(reference).set_tag(0b11)));
+    /// assert!(!ptr.canonical_eq(ptr.clear_tag()));
+    /// ```
+    #[inline]
+    pub fn canonical_eq(self, other: Self) -> bool {
+        self.canonical() == other.canonical()
+    }
+
+    /// Returns `true` if both marked pointers have the same pointer bits,
+    /// regardless of their tag values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let (a, b) = (TagPtr::compose(reference, 0b01), TagPtr::compose(reference, 0b10));
+    ///
+    /// assert_ne!(a, b);
+    /// assert!(a.ptr_eq(b));
+    /// assert!(!a.ptr_eq(TagPtr::compose(&mut 2, 0b01)));
+    /// ```
+    #[inline]
+    pub fn ptr_eq(self, other: Self) -> bool {
+        self.decompose_ptr() == other.decompose_ptr()
+    }
+
+    /// Returns `true` if both marked pointers have the same tag value,
+    /// regardless of their pointer bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let (a, b) = (TagPtr::compose(&mut 1, 0b01), TagPtr::compose(&mut 2, 0b01));
+    ///
+    /// assert_ne!(a, b);
+    /// assert!(a.tag_eq(b));
+    /// assert!(!a.tag_eq(a.set_tag(0b10)));
+    /// ```
+    #[inline]
+    pub fn tag_eq(self, other: Self) -> bool {
+        self.decompose_tag() == other.decompose_tag()
+    }
+
+    /// Returns an iterator over `count` successive element pointers starting
+    /// at the marked pointer (disregarding its tag).
+    ///
+    /// This can be used for walking an array (or arena) referenced by a marked
+    /// pointer.
+    /// The element pointers are computed using wrapping arithmetic, so
+    /// creating the iterator is safe, but the usual restrictions apply for
+    /// dereferencing any of the yielded pointers.
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only* if the pointer is not well aligned for
+    /// `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let mut arena = [1, 2, 3, 4];
+    /// let ptr = TagPtr::compose(arena.as_mut_ptr(), 0b11);
+    ///
+    /// let sum: i32 = ptr.iter_elements(arena.len()).map(|elem| unsafe { *elem }).sum();
+    /// assert_eq!(sum, 10);
+    /// ```
+    #[inline]
+    pub fn iter_elements(self, count: usize) -> impl Iterator<Item = *mut T> {
+        let base = self.decompose_ptr();
+        debug_check!(base.is_aligned(), "base pointer must be well aligned");
+        (0..count).map(move |idx| base.wrapping_add(idx))
+    }
+
+    /// Returns `true` if the marked pointer (disregarding its tag) points into
+    /// the memory range of the given `slice`.
+    ///
+    /// This is a purely diagnostic check based on address comparison, which
+    /// can e.g. be used to assert that a pointer refers into an expected arena.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let arena = [1, 2, 3, 4];
+    /// let ptr = TagPtr::compose(&arena[2] as *const _ as *mut _, 0b11);
+    /// assert!(ptr.points_into(&arena));
+    /// assert!(!ptr.points_into(&arena[..2]));
+    /// ```
+    #[inline]
+    pub fn points_into(self, slice: &[T]) -> bool {
+        slice.as_ptr_range().contains(&(self.decompose_ptr() as *const T))
+    }
+
+    /// Maps the entire marked pointer's address (including its tag) to the
+    /// result of `func`, which is called with the current address.
+    ///
+    /// Unlike a round-trip through [`into_usize`][TagPtr::into_usize] and
+    /// [`from_usize`][TagPtr::from_usize], this preserves the provenance of
+    /// the original pointer (see [`<*mut T>::map_addr`][map_addr]).
+    ///
+    /// [map_addr]: https://doc.rust-lang.org/core/primitive.pointer.html#method.map_addr
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::new(reference).map_addr(|addr| addr | 0b10);
+    /// assert_eq!(ptr.decompose(), (reference as *mut _, 0b10));
+    /// ```
+    #[inline]
+    pub fn map_addr(self, func: impl FnOnce(usize) -> usize) -> Self {
+        Self::new(self.inner.map_addr(func))
+    }
+
     doc_comment! {
         doc_clear_tag!(),
         ///
@@ -224,11 +864,48 @@ impl<T, const N: usize> TagPtr<T, N> {
         /// ```
         #[inline]
         pub fn set_tag(self, tag: usize) -> Self {
-            let ptr = self.decompose_ptr();
-            Self::compose(ptr, tag)
+            self.map_addr(|addr| (addr & Self::POINTER_MASK) | (tag & Self::TAG_MASK))
         }
     }
 
+    /// Returns the marked pointer with its tag value replaced by `tag`.
+    ///
+    /// This is identical to [`set_tag`][TagPtr::set_tag] but named for use in
+    /// builder-style chains.
+    /// Any bits of `tag` exceeding the available tag bits are silently
+    /// truncated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::new(reference).with_tag(0b110);
+    /// assert_eq!(ptr.decompose(), (reference as *mut _, 0b10));
+    /// ```
+    #[inline]
+    pub fn with_tag(self, tag: usize) -> Self {
+        self.set_tag(tag)
+    }
+
+    /// Returns the marked pointer with its pointer bits replaced by `ptr`,
+    /// keeping the current tag value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let (a, b) = (&mut 1, &mut 2);
+    /// let ptr = TagPtr::compose(a, 0b01).with_ptr(b);
+    /// assert_eq!(ptr.decompose(), (b as *mut _, 0b01));
+    /// ```
+    #[inline]
+    pub fn with_ptr(self, ptr: *mut T) -> Self {
+        Self::compose(ptr, self.decompose_tag())
+    }
+
     doc_comment! {
         doc_update_tag!(),
         ///
@@ -244,11 +921,36 @@ impl<T, const N: usize> TagPtr<T, N> {
         /// ```
         #[inline]
         pub fn update_tag(self, func: impl FnOnce(usize) -> usize) -> Self {
-            let (ptr, tag) = self.decompose();
-            Self::compose(ptr, func(tag))
+            self.map_addr(|addr| {
+                let tag = func(addr & Self::TAG_MASK);
+                (addr & Self::POINTER_MASK) | (tag & Self::TAG_MASK)
+            })
         }
     }
 
+    /// Maps the marked pointer's tag value to the result of `func`, which is
+    /// called with the current tag value.
+    ///
+    /// This is identical to [`update_tag`][TagPtr::update_tag], i.e., any
+    /// bits of the result exceeding [`TAG_MASK`][TagPtr::TAG_MASK] are
+    /// silently truncated and the pointer bits are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose(reference, 0b01);
+    ///
+    /// assert_eq!(ptr.map_tag(|tag| tag << 1).decompose(), (reference as *mut _, 0b10));
+    /// assert_eq!(ptr.map_tag(|tag| tag << 2).decompose(), (reference as *mut _, 0b00));
+    /// ```
+    #[inline]
+    pub fn map_tag(self, func: impl FnOnce(usize) -> usize) -> Self {
+        self.update_tag(func)
+    }
+
     doc_comment! {
         doc_add_tag!(),
         ///
@@ -264,7 +966,7 @@ impl<T, const N: usize> TagPtr<T, N> {
         /// ```
         #[inline]
         pub fn add_tag(self, value: usize) -> Self {
-            Self::from_usize(self.into_usize().wrapping_add(value))
+            self.map_addr(|addr| addr.wrapping_add(value))
         }
     }
 
@@ -283,10 +985,211 @@ impl<T, const N: usize> TagPtr<T, N> {
         /// ```
         #[inline]
         pub fn sub_tag(self, value: usize) -> Self {
-            Self::from_usize(self.into_usize().wrapping_sub(value))
+            self.map_addr(|addr| addr.wrapping_sub(value))
+        }
+    }
+
+    /// Adds `value` to the current tag, returning `None` if the result would
+    /// exceed [`TAG_MASK`][TagPtr::TAG_MASK].
+    ///
+    /// Unlike [`add_tag`][TagPtr::add_tag], this never overflows into the
+    /// pointer bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose(reference, 0b10);
+    ///
+    /// assert_eq!(ptr.checked_add_tag(1), Some(TagPtr::compose(reference, 0b11)));
+    /// assert_eq!(ptr.checked_add_tag(2), None);
+    /// ```
+    #[inline]
+    pub fn checked_add_tag(self, value: usize) -> Option<Self> {
+        match self.decompose_tag().checked_add(value) {
+            Some(tag) if tag <= Self::TAG_MASK => Some(self.set_tag(tag)),
+            _ => None,
         }
     }
 
+    /// Subtracts `value` from the current tag, returning `None` if the result
+    /// would be less than zero.
+    ///
+    /// Unlike [`sub_tag`][TagPtr::sub_tag], this never underflows into the
+    /// pointer bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose(reference, 0b01);
+    ///
+    /// assert_eq!(ptr.checked_sub_tag(1), Some(TagPtr::new(reference)));
+    /// assert_eq!(ptr.checked_sub_tag(2), None);
+    /// ```
+    #[inline]
+    pub fn checked_sub_tag(self, value: usize) -> Option<Self> {
+        self.decompose_tag().checked_sub(value).map(|tag| self.set_tag(tag))
+    }
+
+    /// Adds `value` to the current tag, saturating at
+    /// [`TAG_MASK`][TagPtr::TAG_MASK] instead of overflowing.
+    ///
+    /// The pointer bits remain unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose(reference, 0b10);
+    /// assert_eq!(ptr.saturating_add_tag(5).decompose(), (reference as *mut _, 0b11));
+    /// ```
+    #[inline]
+    pub fn saturating_add_tag(self, value: usize) -> Self {
+        let tag = self.decompose_tag().saturating_add(value);
+        self.set_tag(cmp::min(tag, Self::TAG_MASK))
+    }
+
+    /// Subtracts `value` from the current tag, saturating at zero instead of
+    /// underflowing.
+    ///
+    /// The pointer bits remain unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose(reference, 0b10);
+    /// assert_eq!(ptr.saturating_sub_tag(5).decompose(), (reference as *mut _, 0));
+    /// ```
+    #[inline]
+    pub fn saturating_sub_tag(self, value: usize) -> Self {
+        self.set_tag(self.decompose_tag().saturating_sub(value))
+    }
+
+    /// Calculates the offset of the (tag-stripped) pointer by `count`
+    /// elements of `T`, keeping the current tag value.
+    ///
+    /// # Safety
+    ///
+    /// The same safety requirements as for
+    /// [`<*mut T>::offset`][offset] apply to the separated raw pointer.
+    ///
+    /// [offset]: https://doc.rust-lang.org/core/primitive.pointer.html#method.offset
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only* if the resulting pointer is not well
+    /// aligned for `N` tag bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let mut array = [1, 2, 3, 4];
+    /// let ptr = unsafe { TagPtr::compose(array.as_mut_ptr(), 0b11).offset(2) };
+    /// assert_eq!(unsafe { ptr.decompose_ref() }, (Some(&3), 0b11));
+    /// ```
+    #[inline]
+    pub unsafe fn offset(self, count: isize) -> Self {
+        let (ptr, tag) = self.decompose();
+        Self::new(crate::compose::<T, N>(ptr.offset(count), tag))
+    }
+
+    /// Calculates the offset of the (tag-stripped) pointer by `count`
+    /// elements of `T` in positive direction, keeping the current tag value.
+    ///
+    /// # Safety
+    ///
+    /// The same safety requirements as for [`<*mut T>::add`][add] apply to
+    /// the separated raw pointer.
+    ///
+    /// [add]: https://doc.rust-lang.org/core/primitive.pointer.html#method.add
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only* if the resulting pointer is not well
+    /// aligned for `N` tag bits.
+    #[inline]
+    pub unsafe fn add(self, count: usize) -> Self {
+        let (ptr, tag) = self.decompose();
+        Self::new(crate::compose::<T, N>(ptr.add(count), tag))
+    }
+
+    /// Calculates the offset of the (tag-stripped) pointer by `count`
+    /// elements of `T` in negative direction, keeping the current tag value.
+    ///
+    /// # Safety
+    ///
+    /// The same safety requirements as for [`<*mut T>::sub`][sub] apply to
+    /// the separated raw pointer.
+    ///
+    /// [sub]: https://doc.rust-lang.org/core/primitive.pointer.html#method.sub
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only* if the resulting pointer is not well
+    /// aligned for `N` tag bits.
+    #[inline]
+    pub unsafe fn sub(self, count: usize) -> Self {
+        let (ptr, tag) = self.decompose();
+        Self::new(crate::compose::<T, N>(ptr.sub(count), tag))
+    }
+
+    /// Performs a volatile read of the marked pointer stored at `src`.
+    ///
+    /// This reads the single-word representation of the marked pointer
+    /// through [`ptr::read_volatile`] and is intended for memory-mapped I/O.
+    /// Volatile operations are not synchronized and must not be used for
+    /// sharing marked pointers between threads, for which
+    /// [`AtomicTagPtr`][crate::AtomicTagPtr] should be used instead.
+    ///
+    /// # Safety
+    ///
+    /// The same safety requirements as for [`ptr::read_volatile`] apply to
+    /// `src`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let slot = TagPtr::compose(reference, 0b01);
+    /// let ptr = unsafe { TagPtr::read_volatile(&slot) };
+    /// assert_eq!(ptr.decompose(), (reference as *mut _, 0b01));
+    /// ```
+    #[inline]
+    pub unsafe fn read_volatile(src: *const Self) -> Self {
+        ptr::read_volatile(src)
+    }
+
+    /// Performs a volatile write of `value` to the marked pointer stored at
+    /// `dst`.
+    ///
+    /// This writes the single-word representation of the marked pointer
+    /// through [`ptr::write_volatile`] and is intended for memory-mapped I/O.
+    /// Like [`read_volatile`][TagPtr::read_volatile], this is not
+    /// synchronized.
+    ///
+    /// # Safety
+    ///
+    /// The same safety requirements as for [`ptr::write_volatile`] apply to
+    /// `dst`.
+    #[inline]
+    pub unsafe fn write_volatile(dst: *mut Self, value: Self) {
+        ptr::write_volatile(dst, value)
+    }
+
     doc_comment! {
         doc_decompose!(),
         #[inline]
@@ -295,11 +1198,29 @@ impl<T, const N: usize> TagPtr<T, N> {
         }
     }
 
+    /// Decomposes the marked pointer, returning the raw pointer and the
+    /// separated tag value as named fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let parts = TagPtr::compose(reference, 0b10).decompose_parts();
+    /// assert_eq!(parts.ptr, reference as *mut _);
+    /// assert_eq!(parts.tag, 0b10);
+    /// ```
+    #[inline]
+    pub fn decompose_parts(self) -> DecomposedPtr<T> {
+        DecomposedPtr { ptr: self.decompose_ptr(), tag: self.decompose_tag() }
+    }
+
     doc_comment! {
         doc_decompose_ptr!(),
         #[inline]
         pub fn decompose_ptr(self) -> *mut T {
-            crate::decompose_ptr::<T>(self.inner as usize, Self::TAG_BITS)
+            crate::decompose_ptr(self.inner, Self::TAG_BITS)
         }
     }
 
@@ -307,10 +1228,114 @@ impl<T, const N: usize> TagPtr<T, N> {
         doc_decompose_tag!(),
         #[inline]
         pub fn decompose_tag(self) -> usize {
-            crate::decompose_tag::<T>(self.inner as usize, Self::TAG_BITS)
+            crate::decompose_tag(self.inner.addr(), Self::TAG_BITS)
         }
     }
 
+    /// Decomposes the integer representation `val` of a marked pointer (see
+    /// [`into_usize`][TagPtr::into_usize]), returning only the separated tag
+    /// value.
+    ///
+    /// Unlike [`decompose_tag`][TagPtr::decompose_tag], this is a `const fn`
+    /// and can hence be used for computing tag values at compile time.
+    /// The methods decomposing an actual marked pointer can not be `const`,
+    /// since the address of a pointer can not be read during constant
+    /// evaluation on stable Rust.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// const TAG: usize = TagPtr::tag_from_usize(0x1000 | 0b11);
+    /// assert_eq!(TAG, 0b11);
+    /// ```
+    #[inline]
+    pub const fn tag_from_usize(val: usize) -> usize {
+        crate::decompose_tag(val, Self::TAG_BITS)
+    }
+
+    /// Returns the separated tag value.
+    ///
+    /// This is an alias for [`decompose_tag`][TagPtr::decompose_tag].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let ptr = TagPtr::compose(&mut 1, 0b10);
+    /// assert_eq!(ptr.tag(), 0b10);
+    /// ```
+    #[inline]
+    pub fn tag(self) -> usize {
+        self.decompose_tag()
+    }
+
+    /// Returns `true` if any of the marked pointer's tag bits are set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// assert!(!TagPtr::compose(reference, 0b00).is_tagged());
+    /// assert!(TagPtr::compose(reference, 0b10).is_tagged());
+    /// ```
+    #[inline]
+    pub fn is_tagged(self) -> bool {
+        self.decompose_tag() != 0
+    }
+
+    /// Decomposes the marked pointer, returning the raw pointer and the
+    /// separated tag value converted into a set of flags.
+    ///
+    /// See [`compose_flags`][TagPtr::compose_flags] for details.
+    #[inline]
+    pub fn decompose_flags<F: From<usize>>(self) -> (*mut T, F) {
+        let (ptr, tag) = self.decompose();
+        (ptr, F::from(tag))
+    }
+
+    /// Decomposes the marked pointer, returning the raw pointer and the
+    /// separated tag value as a set of flags generated by the `bitflags`
+    /// crate.
+    ///
+    /// Any tag bits not corresponding to a defined flag are truncated.
+    /// See [`compose_bitflags`][TagPtr::compose_bitflags] for details.
+    #[cfg(feature = "bitflags")]
+    #[inline]
+    pub fn decompose_bitflags<F: bitflags::Flags<Bits = usize>>(self) -> (*mut T, F) {
+        let (ptr, tag) = self.decompose();
+        (ptr, F::from_bits_truncate(tag))
+    }
+
+    /// Decomposes the marked pointer, returning the separated (tag-stripped)
+    /// address and the tag value.
+    ///
+    /// The address does not carry the pointer's provenance and is hence only
+    /// suitable for introspection purposes such as hashing or serialization.
+    /// It must not be cast back to a pointer for dereferencing without
+    /// re-establishing its provenance, e.g., through
+    /// `with_addr`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<i32, 2>;
+    ///
+    /// let reference = &mut 1;
+    /// let ptr = TagPtr::compose(reference, 0b11);
+    ///
+    /// assert_eq!(ptr.addr_and_tag(), (reference as *mut i32 as usize, 0b11));
+    /// ```
+    #[inline]
+    pub fn addr_and_tag(self) -> (usize, usize) {
+        let (ptr, tag) = self.decompose();
+        (ptr.addr(), tag)
+    }
+
     doc_comment! {
         doc_as_ref!("nullable"),
         ///
@@ -402,12 +1427,78 @@ impl<T, const N: usize> TagPtr<T, N> {
     }
 }
 
+/********** impl inherent (function pointers) *****************************************************/
+
+impl<const N: usize> TagPtr<(), N> {
+    /// Composes a new type-erased marked pointer from the function pointer
+    /// `func` and a `tag` value.
+    ///
+    /// Unlike for data pointers, the alignment of functions is not determined
+    /// by any type but varies by target (and potentially by optimization
+    /// level), so it is the caller's responsibility to ensure function
+    /// addresses always have at least `N` unused lower bits.
+    /// The same caveats as for [`compose`][TagPtr::compose] apply otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type TagPtr = tagptr::TagPtr<(), 1>;
+    ///
+    /// fn callback() {}
+    ///
+    /// let func: fn() = callback;
+    /// // the alignment of function addresses is not guaranteed
+    /// if func as usize & TagPtr::TAG_MASK == 0 {
+    ///     let ptr = TagPtr::from_fn_ptr(func, 0b1);
+    ///     assert_eq!(ptr.decompose_tag(), 0b1);
+    ///
+    ///     let func = unsafe { ptr.as_fn_ptr() };
+    ///     func();
+    /// }
+    /// ```
+    #[inline]
+    pub fn from_fn_ptr(func: fn(), tag: usize) -> Self {
+        Self::new(crate::compose::<(), N>(func as *mut (), tag))
+    }
+
+    /// Decomposes the marked pointer, returning only the separated function
+    /// pointer.
+    ///
+    /// # Safety
+    ///
+    /// The caller has to ensure that the marked pointer was created from a
+    /// valid function pointer of type `fn()`, e.g., through
+    /// [`from_fn_ptr`][TagPtr::from_fn_ptr].
+    /// In particular, the pointer must not be `null`.
+    #[inline]
+    pub unsafe fn as_fn_ptr(self) -> fn() {
+        mem::transmute::<*mut (), fn()>(self.decompose_ptr())
+    }
+}
+
 /********** impl Debug ****************************************************************************/
 
 impl<T, const N: usize> fmt::Debug for TagPtr<T, N> {
     impl_debug!("TagPtr");
 }
 
+/********** impl Display **************************************************************************/
+
+/// Formats the marked pointer as its (tag-stripped) address in hexadecimal
+/// and its tag value in binary, padded to `N` digits.
+///
+/// # Examples
+///
+/// ```
+/// type TagPtr = tagptr::TagPtr<u64, 3>;
+///
+/// let ptr = TagPtr::from_usize(0x1000 | 0b11);
+/// assert_eq!(ptr.to_string(), "0x1000@0b011");
+/// ```
+impl<T, const N: usize> fmt::Display for TagPtr<T, N> {
+    impl_display!();
+}
+
 /********** impl Default **************************************************************************/
 
 impl<T, const N: usize> Default for TagPtr<T, N> {
@@ -492,6 +1583,28 @@ impl<T, const N: usize> PartialEq for TagPtr<T, N> {
     impl_partial_eq!();
 }
 
+/********** impl PartialEq (*mut T) ***************************************************************/
+
+/// Compares only the marked pointer's (tag-stripped) pointer bits with the raw
+/// pointer, i.e., the tag value is ignored.
+impl<T, const N: usize> PartialEq<*mut T> for TagPtr<T, N> {
+    #[inline]
+    fn eq(&self, other: &*mut T) -> bool {
+        self.decompose_ptr() == *other
+    }
+}
+
+/********** impl PartialEq (TagPtr) for *mut T ****************************************************/
+
+/// Compares the raw pointer only with the marked pointer's (tag-stripped)
+/// pointer bits, i.e., the tag value is ignored.
+impl<T, const N: usize> PartialEq<TagPtr<T, N>> for *mut T {
+    #[inline]
+    fn eq(&self, other: &TagPtr<T, N>) -> bool {
+        *self == other.decompose_ptr()
+    }
+}
+
 /********** impl PartialOrd ***********************************************************************/
 
 impl<T, const N: usize> PartialOrd for TagPtr<T, N> {
@@ -520,6 +1633,163 @@ impl<T, const N: usize> Hash for TagPtr<T, N> {
     impl_hash!();
 }
 
+/********** impl Zeroable *************************************************************************/
+
+/// The all-zeros bit pattern is an unmarked `null` pointer.
+#[cfg(feature = "bytemuck")]
+unsafe impl<T, const N: usize> bytemuck::Zeroable for TagPtr<T, N> {}
+
+/********** impl Pod ******************************************************************************/
+
+/// Since [`TagPtr`] is a transparent wrapper around a single `*mut T`, any bit
+/// pattern is a valid (but not necessarily dereferenceable) marked pointer.
+///
+/// Implementing `Pod` for a pointer type is unusual: Marked pointers created
+/// from arbitrary bytes have no provenance and must never be dereferenced.
+/// It is the user's responsibility to only dereference pointers recovered
+/// from bytes, which were derived from valid pointers with exposed
+/// provenance (see [`from_usize`][TagPtr::from_usize]).
+#[cfg(feature = "bytemuck")]
+unsafe impl<T: 'static, const N: usize> bytemuck::Pod for TagPtr<T, N> {}
+
+/********** impl Serialize ************************************************************************/
+
+/// Serializes the marked pointer as a tuple of its (tag-stripped) address and
+/// its tag value.
+///
+/// The address is serialized as a plain `usize`, which is only meaningful
+/// within the same process.
+/// It is the user's responsibility to ensure that a deserialized pointer is
+/// still valid before it is dereferenced.
+#[cfg(feature = "serde")]
+impl<T, const N: usize> serde::Serialize for TagPtr<T, N> {
+    #[inline]
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (ptr, tag) = self.decompose();
+        (ptr.expose_provenance(), tag).serialize(serializer)
+    }
+}
+
+/********** impl Deserialize **********************************************************************/
+
+/// Deserializes a marked pointer from a tuple of its (tag-stripped) address
+/// and its tag value.
+///
+/// Fails, if the address is not sufficiently aligned for `N` tag bits or if
+/// the tag value exceeds [`TAG_MASK`][TagPtr::TAG_MASK].
+#[cfg(feature = "serde")]
+impl<'de, T, const N: usize> serde::Deserialize<'de> for TagPtr<T, N> {
+    #[inline]
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let (addr, tag) = <(usize, usize)>::deserialize(deserializer)?;
+        Self::try_compose(ptr::with_exposed_provenance_mut(addr), tag).map_err(|err| match err {
+            ComposeError::Unaligned(_) => {
+                D::Error::custom("address is mis-aligned for `N` tag bits")
+            }
+            ComposeError::TagOverflow(_) => D::Error::custom("tag value exceeds `N` tag bits"),
+        })
+    }
+}
+
+/********** impl Clone (ByPtr) ********************************************************************/
+
+impl<T, const N: usize> Clone for ByPtr<T, N> {
+    impl_clone!();
+}
+
+/********** impl Copy (ByPtr) *********************************************************************/
+
+impl<T, const N: usize> Copy for ByPtr<T, N> {}
+
+/********** impl Debug (ByPtr) ********************************************************************/
+
+impl<T, const N: usize> fmt::Debug for ByPtr<T, N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("ByPtr").field(&self.0).finish()
+    }
+}
+
+/********** impl From (TagPtr) ********************************************************************/
+
+impl<T, const N: usize> From<TagPtr<T, N>> for ByPtr<T, N> {
+    #[inline]
+    fn from(ptr: TagPtr<T, N>) -> Self {
+        Self(ptr)
+    }
+}
+
+/********** impl PartialEq (ByPtr) ****************************************************************/
+
+impl<T, const N: usize> PartialEq for ByPtr<T, N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0.decompose_ptr() == other.0.decompose_ptr()
+    }
+}
+
+/********** impl Eq (ByPtr) ***********************************************************************/
+
+impl<T, const N: usize> Eq for ByPtr<T, N> {}
+
+/********** impl PartialOrd (ByPtr) ***************************************************************/
+
+impl<T, const N: usize> PartialOrd for ByPtr<T, N> {
+    impl_partial_ord!();
+}
+
+/********** impl Ord (ByPtr) **********************************************************************/
+
+impl<T, const N: usize> Ord for ByPtr<T, N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.0.decompose_ptr().cmp(&other.0.decompose_ptr())
+    }
+}
+
+/********** impl Hash (ByPtr) *********************************************************************/
+
+impl<T, const N: usize> Hash for ByPtr<T, N> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.decompose_ptr().hash(state)
+    }
+}
+
+/********** impl Clone (DecomposedPtr) ************************************************************/
+
+impl<T> Clone for DecomposedPtr<T> {
+    impl_clone!();
+}
+
+/********** impl Copy (DecomposedPtr) *************************************************************/
+
+impl<T> Copy for DecomposedPtr<T> {}
+
+/********** impl Debug (DecomposedPtr) ************************************************************/
+
+impl<T> fmt::Debug for DecomposedPtr<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DecomposedPtr").field("ptr", &self.ptr).field("tag", &self.tag).finish()
+    }
+}
+
+/********** impl PartialEq (DecomposedPtr) ********************************************************/
+
+impl<T> PartialEq for DecomposedPtr<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr && self.tag == other.tag
+    }
+}
+
+/********** impl Eq (DecomposedPtr) ***************************************************************/
+
+impl<T> Eq for DecomposedPtr<T> {}
+
 #[cfg(test)]
 mod tests {
     type TagPtr = crate::TagPtr<i32, 2>;
@@ -534,6 +1804,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_display() {
+        let reference = &mut 1;
+        let ptr = TagPtr::compose(reference, 0b01);
+        assert_eq!(std::format!("{}", ptr), std::format!("{:p}@0b01", reference as *mut _));
+        assert_eq!(std::format!("{}", TagPtr::null()), "0x0@0b00");
+        assert_eq!(std::format!("{}", crate::TagPtr::<i32, 0>::null()), "0x0@0b0");
+    }
+
     #[test]
     fn test_cast() {
         type ErasedPtr = crate::TagPtr<(), 2>;
@@ -553,6 +1832,144 @@ mod tests {
         assert_eq!(ptr.decompose(), (reference as *const _ as *mut _, 0b1));
     }
 
+    #[test]
+    fn test_tag_from_usize() {
+        const TAG: usize = TagPtr::tag_from_usize(0b11);
+        assert_eq!(TAG, 0b11);
+
+        let ptr = TagPtr::compose(&mut 1, 0b10);
+        assert_eq!(TagPtr::tag_from_usize(ptr.into_usize()), ptr.decompose_tag());
+    }
+
+    #[test]
+    fn test_as_non_null() {
+        let reference = &mut 1;
+        let ptr = TagPtr::compose(reference, 0b01);
+        assert_eq!(ptr.as_non_null().map(|ptr| ptr.into_marked_ptr()), Some(ptr));
+        assert_eq!(unsafe { ptr.as_non_null_unchecked() }.decompose_tag(), 0b01);
+        assert!(TagPtr::null().as_non_null().is_none());
+        assert!(TagPtr::from_usize(0b11).as_non_null().is_none());
+    }
+
+    #[test]
+    fn test_convert() {
+        use crate::ComposeError;
+
+        let reference = &mut 1;
+
+        // narrowing that discards set tag bits
+        let ptr = TagPtr::compose(reference, 0b11);
+        assert_eq!(ptr.convert::<1>().decompose(), (reference as *mut _, 0b1));
+        assert_eq!(ptr.try_convert::<1>(), Err(ComposeError::TagOverflow(0b11)));
+        assert_eq!(ptr.convert::<0>().into_raw(), reference as *mut _);
+
+        // narrowing that does not discard any set tag bits
+        let ptr = TagPtr::compose(reference, 0b01);
+        assert_eq!(ptr.convert::<1>().decompose(), (reference as *mut _, 0b1));
+        assert_eq!(ptr.try_convert::<1>(), Ok(ptr.convert::<1>()));
+        assert_eq!(ptr.convert::<2>(), ptr);
+    }
+
+    #[test]
+    fn test_volatile() {
+        let reference = &mut 1;
+        let mut slot = TagPtr::null();
+
+        unsafe {
+            TagPtr::write_volatile(&mut slot, TagPtr::compose(reference, 0b10));
+            let ptr = TagPtr::read_volatile(&slot);
+            assert_eq!(ptr.decompose(), (reference as *mut _, 0b10));
+            *ptr.as_mut().unwrap() += 1;
+        }
+
+        assert_eq!(*reference, 2);
+    }
+
+    #[test]
+    fn test_by_ptr() {
+        use std::collections::{BTreeSet, HashSet};
+
+        use crate::ByPtr;
+
+        let (a, b) = (&mut 1, &mut 2);
+        let ptrs = [TagPtr::compose(a, 0b01), TagPtr::compose(a, 0b10), TagPtr::compose(b, 0b01)];
+
+        let set: HashSet<_> = ptrs[..2].iter().copied().map(ByPtr).collect();
+        assert_eq!(set.len(), 1);
+        let set: BTreeSet<_> = ptrs.iter().copied().map(ByPtr).collect();
+        assert_eq!(set.len(), 2);
+        assert_eq!(ptrs[0].cmp(&ptrs[1]), std::cmp::Ordering::Less);
+    }
+
+    #[test]
+    fn test_map_addr_provenance() {
+        let mut value = 1;
+        let ptr = TagPtr::new(&mut value).set_tag(0b01).set_tag(0b11).add_tag(0).sub_tag(1);
+        assert_eq!(ptr.map_addr(|addr| addr & !0b11), ptr.clear_tag());
+
+        // the pointer must remain dereferenceable after any tag modifications
+        let ptr = ptr.update_tag(|tag| tag + 1).set_tag(0b10);
+        unsafe { *ptr.as_mut().unwrap() += 1 };
+        assert_eq!(value, 2);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn test_bytemuck() {
+        let ptrs = [TagPtr::from_usize(0x1000 | 0b01), TagPtr::from_usize(0x2000 | 0b10)];
+
+        let words: &[usize] = bytemuck::cast_slice(&ptrs);
+        assert_eq!(words, [0x1001, 0x2002]);
+        let ptrs: &[TagPtr] = bytemuck::cast_slice(words);
+        assert_eq!(ptrs[1].decompose(), (core::ptr::without_provenance_mut(0x2000), 0b10));
+
+        let zeroed: [TagPtr; 2] = bytemuck::Zeroable::zeroed();
+        assert!(zeroed.iter().all(|ptr| ptr.is_null() && ptr.decompose_tag() == 0));
+    }
+
+    #[cfg(all(feature = "serde", target_pointer_width = "64"))]
+    #[test]
+    fn test_serde() {
+        use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+        let ptr = TagPtr::compose(core::ptr::without_provenance_mut(0x1000), 0b10);
+        let tokens =
+            [Token::Tuple { len: 2 }, Token::U64(0x1000), Token::U64(0b10), Token::TupleEnd];
+        assert_tokens(&ptr, &tokens);
+        assert_tokens(&ptr.set_tag(0b11), &[tokens[0], tokens[1], Token::U64(0b11), tokens[3]]);
+
+        assert_de_tokens_error::<TagPtr>(
+            &[tokens[0], tokens[1], Token::U64(0b100), tokens[3]],
+            "tag value exceeds `N` tag bits",
+        );
+        assert_de_tokens_error::<TagPtr>(
+            &[tokens[0], Token::U64(0x1001), tokens[2], tokens[3]],
+            "address is mis-aligned for `N` tag bits",
+        );
+    }
+
+    #[test]
+    fn test_usize_roundtrip() {
+        let mut value = 1;
+        let val = TagPtr::compose(&mut value, 0b10).into_usize();
+
+        // the pointer recovered from the integer must be dereferenceable
+        let (ptr, tag) = unsafe { TagPtr::from_usize(val).decompose_mut() };
+        *ptr.unwrap() += tag as i32;
+        assert_eq!(value, 3);
+    }
+
+    #[test]
+    fn test_bit_layout() {
+        type TagPtr = crate::TagPtr<u64, 3>;
+
+        let ptr = TagPtr::compose(0xdead_bee8 as *mut u64, 0b101);
+        assert_eq!(ptr.into_bits(), 0xdead_beed);
+        assert_eq!(TagPtr::POINTER_MASK, !0b111);
+        assert_eq!(ptr.into_bits() & TagPtr::POINTER_MASK, 0xdead_bee8);
+        assert_eq!(ptr.into_bits() & TagPtr::TAG_MASK, 0b101);
+    }
+
     #[test]
     fn test_compose() {
         let reference = &mut 1;
@@ -563,6 +1980,82 @@ mod tests {
         assert_eq!(ptr2.decompose(), (reference as *mut _, 0b11));
     }
 
+    #[test]
+    #[cfg(all(debug_assertions, not(feature = "no-panic")))]
+    #[should_panic(expected = "tag bits in raw pointer must be zeroed")]
+    fn test_compose_misaligned() {
+        // a pointer to a byte buffer element is not aligned for `i32`
+        let mut bytes = [0u8; 8];
+        let misaligned = bytes.as_mut_ptr().map_addr(|addr| addr | 0b1).cast::<i32>();
+        let _ = TagPtr::compose(misaligned, 0);
+    }
+
+    #[test]
+    fn test_zero_tag_bits() {
+        type TagPtr = crate::TagPtr<i32, 0>;
+        assert_eq!((TagPtr::TAG_MASK, TagPtr::POINTER_MASK), (0, usize::MAX));
+
+        let reference = &mut 1;
+        let raw = reference as *mut i32;
+        let ptr = TagPtr::compose(raw, usize::MAX);
+        assert_eq!(ptr.into_raw(), raw);
+        assert_eq!(ptr.decompose(), (raw, 0));
+        assert_eq!(ptr.set_tag(0b1).update_tag(|_| 0b11), ptr);
+        assert_eq!(ptr.checked_add_tag(1), None);
+        assert_eq!(ptr.saturating_add_tag(1), ptr);
+        assert!(!ptr.is_tagged() && ptr.tag_bits().next().is_none());
+        assert_eq!(crate::TagNonNull::new(ptr).unwrap().decompose_tag(), 0);
+    }
+
+    #[test]
+    fn test_try_compose() {
+        use crate::ComposeError;
+
+        // u16 has an alignment of 2 and hence only one free tag bit
+        type TagPtr = crate::TagPtr<u16, 3>;
+
+        #[repr(align(8))]
+        struct Aligned([u16; 4]);
+
+        let mut array = Aligned([0; 4]);
+        let base = array.0.as_mut_ptr();
+
+        assert_eq!(TagPtr::try_compose(base, 0b111).map(TagPtr::decompose), Ok((base, 0b111)));
+        assert_eq!(TagPtr::try_compose(base, 0b1000), Err(ComposeError::TagOverflow(0b1000)));
+        for i in 1..4 {
+            let ptr = base.wrapping_add(i);
+            assert_eq!(TagPtr::try_compose(ptr, 0), Err(ComposeError::Unaligned(ptr as usize)));
+        }
+
+        // zero-sized types have no alignment guarantees but are checked at runtime
+        let erased = base.cast::<()>();
+        let res = crate::TagPtr::<(), 3>::try_compose(erased, 0b101).map(crate::TagPtr::decompose);
+        assert_eq!(res, Ok((erased, 0b101)));
+
+        assert_eq!(
+            std::format!("{}", ComposeError::Unaligned(0x1002)),
+            "pointer has tag bits set (address: 0x1002)"
+        );
+        assert_eq!(
+            std::format!("{}", ComposeError::TagOverflow(0b1000)),
+            "tag value exceeds the available tag bits (tag: 0b1000)"
+        );
+    }
+
+    #[test]
+    fn test_eq_raw_ptr() {
+        let (a, b) = (&mut 1, &mut 2);
+        let (a, b): (*mut i32, *mut i32) = (a, b);
+
+        for tag in 0..=TagPtr::TAG_MASK {
+            let ptr = TagPtr::compose(a, tag);
+            assert_eq!(ptr, a);
+            assert_eq!(a, ptr);
+            assert_ne!(ptr, b);
+            assert_ne!(b, ptr);
+        }
+    }
+
     #[test]
     fn test_set_tag() {
         let reference = &mut 1;
@@ -571,6 +2064,65 @@ mod tests {
         assert_eq!(ptr, ptr.set_tag(0b111));
     }
 
+    #[test]
+    fn test_with_ptr_and_tag() {
+        let (a, b) = (&mut 1, &mut 2);
+        let (a, b): (*mut i32, *mut i32) = (a, b);
+
+        let base = TagPtr::compose(a, 0b11);
+        assert_eq!(base.with_ptr(b).decompose(), (b, 0b11));
+        assert_eq!(base.with_ptr(b).with_tag(0b10).decompose(), (b, 0b10));
+        assert_eq!(base.with_tag(0b01).with_ptr(b), TagPtr::compose(b, 0b01));
+        assert_eq!(base.with_tag(0b101).decompose_tag(), 0b01);
+    }
+
+    #[cfg(feature = "bitflags")]
+    #[test]
+    fn test_flags() {
+        bitflags::bitflags! {
+            #[derive(Clone, Copy, Debug, PartialEq)]
+            struct Flags: usize {
+                const MARKED = 0b01;
+                const LOCKED = 0b10;
+            }
+        }
+
+        impl From<Flags> for usize {
+            fn from(flags: Flags) -> Self {
+                flags.bits()
+            }
+        }
+
+        impl From<usize> for Flags {
+            fn from(tag: usize) -> Self {
+                Self::from_bits_truncate(tag)
+            }
+        }
+
+        let reference = &mut 1;
+        let ptr = TagPtr::compose_flags(reference, Flags::MARKED | Flags::LOCKED);
+        assert_eq!(ptr.decompose(), (reference as *mut _, 0b11));
+        assert_eq!(ptr.decompose_flags(), (reference as *mut _, Flags::all()));
+
+        let ptr = ptr.set_tag(0b10);
+        assert_eq!(ptr.decompose_flags(), (reference as *mut _, Flags::LOCKED));
+
+        let ptr = TagPtr::compose_bitflags(reference, Flags::MARKED);
+        assert_eq!(ptr.decompose(), (reference as *mut _, 0b01));
+        assert_eq!(ptr.set_tag(0b11).decompose_bitflags(), (reference as *mut _, Flags::all()));
+    }
+
+    #[test]
+    fn test_map_tag() {
+        let reference = &mut 1;
+        let ptr = TagPtr::compose(reference, 0b11);
+
+        // map_tag must truncate the mapped tag to TAG_MASK
+        assert_eq!(ptr.map_tag(|tag| tag + 0b110).decompose(), (reference as *mut _, 0b01));
+        assert_eq!(ptr.map_tag(|_| usize::MAX).decompose(), (reference as *mut _, 0b11));
+        assert_eq!(ptr.map_tag(|tag| tag + 1), ptr.update_tag(|tag| tag + 1));
+    }
+
     #[test]
     fn test_overflow_tag() {
         let reference = &mut 1;
@@ -596,9 +2148,181 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_offset() {
+        let mut array = [1, 2, 3, 4];
+        let base = array.as_mut_ptr();
+        let ptr = TagPtr::compose(base, 0b11);
+
+        unsafe {
+            assert_eq!(ptr.add(2).decompose(), (base.add(2), 0b11));
+            assert_eq!(ptr.offset(2), ptr.add(2));
+            assert_eq!(ptr.add(3).sub(1), ptr.add(2));
+            assert_eq!(ptr.add(3).offset(-3), ptr);
+
+            *ptr.add(2).as_mut().unwrap() = 5;
+        }
+
+        assert_eq!(array, [1, 2, 5, 4]);
+    }
+
+    #[test]
+    fn test_tag_bits() {
+        let reference = &mut 1;
+        let ptr = TagPtr::new(reference);
+
+        assert!(ptr.set_tag_bit(1).tag_bit(1));
+        assert!(!ptr.set_tag_bit(1).tag_bit(0));
+        assert_eq!(ptr.set_tag_bit(1).decompose(), (reference as *mut _, 0b10));
+        assert_eq!(ptr.set_tag_bit(1).set_tag_bit(1), ptr.set_tag_bit(1));
+        assert_eq!(ptr.set_tag_bit(0).set_tag_bit(1).clear_tag_bit(0).decompose_tag(), 0b10);
+        assert_eq!(ptr.clear_tag_bit(1), ptr);
+        assert_eq!(ptr.toggle_tag_bit(0).toggle_tag_bit(0), ptr);
+        assert!(ptr.toggle_tag_bit(1).tag_bit(1));
+    }
+
+    #[test]
+    fn test_tag_bits_iter() {
+        type TagPtr = crate::TagPtr<u64, 3>;
+        let ptr = TagPtr::compose(&mut 1, 0b101);
+
+        assert!(ptr.tag_bits().eq([0, 2]));
+        assert_eq!(ptr.clear_tag().tag_bits().next(), None);
+        assert!(ptr.set_tag(0b111).tag_bits().eq(0..3));
+    }
+
+    #[test]
+    #[cfg(all(debug_assertions, not(feature = "no-panic")))]
+    #[should_panic(expected = "`bit` exceeds tag bits")]
+    fn test_set_tag_bit_out_of_range() {
+        let _ = TagPtr::null().set_tag_bit(2);
+    }
+
+    #[test]
+    fn test_checked_tag() {
+        let reference = &mut 1;
+        let ptr = TagPtr::compose(reference, 0b11);
+
+        assert_eq!(ptr.checked_add_tag(1), None);
+        assert_eq!(ptr.checked_add_tag(usize::MAX), None);
+        assert_eq!(ptr.checked_add_tag(0), Some(ptr));
+        assert_eq!(ptr.checked_sub_tag(3), Some(TagPtr::new(reference)));
+        assert_eq!(ptr.checked_sub_tag(4), None);
+        assert_eq!(TagPtr::new(reference).checked_sub_tag(1), None);
+    }
+
+    #[test]
+    fn test_saturating_tag() {
+        let reference = &mut 1;
+        let ptr = TagPtr::compose(reference, 0b01);
+
+        assert_eq!(ptr.saturating_add_tag(1).decompose(), (reference as *mut _, 0b10));
+        assert_eq!(ptr.saturating_add_tag(usize::MAX).decompose(), (reference as *mut _, 0b11));
+        assert_eq!(ptr.saturating_sub_tag(usize::MAX).decompose(), (reference as *mut _, 0));
+        assert!(ptr.saturating_add_tag(2).tag_is_saturated());
+    }
+
+    #[test]
+    fn test_tag_is_saturated() {
+        let reference = &mut 1;
+        let ptr = TagPtr::compose(reference, TagPtr::TAG_MASK);
+        assert!(ptr.tag_is_saturated());
+        assert!(!ptr.sub_tag(1).tag_is_saturated());
+        assert!(!TagPtr::null().tag_is_saturated());
+    }
+
+    #[test]
+    fn test_log_repr() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        let reference = &mut 1;
+        let repr = TagPtr::compose(reference, 0b10).log_repr();
+        assert_send_sync(&repr);
+
+        let addr = reference as *mut i32 as usize;
+        assert_eq!(repr, crate::LogRepr { addr, tag: 0b10 });
+        assert_eq!(std::format!("{}", repr), std::format!("{:#x}@0b10", addr));
+    }
+
+    #[test]
+    fn test_shard_index() {
+        let nodes = &mut [0; 64];
+        for shards in [1, 3, 8] {
+            for node in nodes.iter_mut() {
+                let ptr = TagPtr::new(node);
+                let shard = ptr.shard_index(shards);
+                assert!(shard < shards);
+                for tag in 1..=TagPtr::TAG_MASK {
+                    assert_eq!(ptr.set_tag(tag).shard_index(shards), shard);
+                }
+            }
+        }
+
+        // consecutive nodes should not all be assigned to the same shard
+        let first = TagPtr::new(&mut nodes[0]).shard_index(8);
+        assert!(nodes.iter_mut().any(|node| TagPtr::new(node).shard_index(8) != first));
+    }
+
+    #[test]
+    fn test_canonical_eq() {
+        let (a, b) = (&mut 1, &mut 2);
+        let ptrs = [
+            TagPtr::null(),
+            TagPtr::compose(a, 0b01),
+            TagPtr::compose(a, 0b10),
+            TagPtr::compose(b, 0b01),
+            TagPtr::from_usize(0b11),
+        ];
+
+        for &lhs in &ptrs {
+            for &rhs in &ptrs {
+                assert_eq!(lhs.canonical_eq(rhs), lhs.decompose() == rhs.decompose());
+            }
+        }
+    }
+
+    #[test]
+    fn test_points_into() {
+        let arena = [1, 2, 3, 4];
+        let other = 5;
+
+        let end = TagPtr::compose(arena.as_ptr_range().end as *mut _, 0b1);
+        let outside = TagPtr::compose(&other as *const _ as *mut _, 0b1);
+
+        assert!(TagPtr::compose(arena.as_ptr() as *mut _, 0b11).points_into(&arena));
+        assert!(!end.points_into(&arena));
+        assert!(!outside.points_into(&arena));
+        assert!(!TagPtr::null().points_into(&arena));
+    }
+
+    // the alignment of function addresses is not guaranteed: Miri places
+    // functions at arbitrary (potentially odd) addresses, while native code
+    // generation aligns them in practice, which is asserted below
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_fn_ptr() {
+        use core::sync::atomic::{AtomicBool, Ordering};
+
+        static CALLED: AtomicBool = AtomicBool::new(false);
+        fn callback() {
+            CALLED.store(true, Ordering::Relaxed);
+        }
+
+        let func: fn() = callback;
+        assert_eq!(func as usize & 0b1, 0, "function address must be 2-byte aligned");
+
+        let ptr = crate::TagPtr::<(), 1>::from_fn_ptr(func, 0b1);
+        assert_eq!(ptr.decompose(), (func as *mut (), 0b1));
+
+        let func = unsafe { ptr.as_fn_ptr() };
+        func();
+        assert!(CALLED.load(Ordering::Relaxed));
+    }
+
     #[test]
     fn test_erase() {
         #[repr(align(64))]
+        #[allow(dead_code)]
         struct Aligned64(i32);
 
         let reference = &Aligned64(1);
@@ -0,0 +1,204 @@
+use core::{
+    cell::UnsafeCell,
+    fmt, hint, ptr,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::AtomicStampedPtr;
+
+/********** impl Send + Sync **********************************************************************/
+
+// SAFETY: all accesses to the inner pair are synchronized through the lock
+unsafe impl<T> Send for AtomicStampedPtr<T> {}
+// SAFETY: all accesses to the inner pair are synchronized through the lock
+unsafe impl<T> Sync for AtomicStampedPtr<T> {}
+
+/********** impl inherent *************************************************************************/
+
+impl<T> AtomicStampedPtr<T> {
+    /// Creates a new `null` pointer with a stamp of zero.
+    #[inline]
+    pub const fn null() -> Self {
+        Self::new(ptr::null_mut(), 0)
+    }
+
+    /// Creates a new atomic pointer from `ptr` and `stamp`.
+    #[inline]
+    pub const fn new(ptr: *mut T, stamp: u64) -> Self {
+        Self { lock: AtomicBool::new(false), inner: UnsafeCell::new((ptr, stamp)) }
+    }
+
+    /// Consumes the atomic pointer and returns the contained pointer and
+    /// stamp.
+    #[inline]
+    pub fn into_inner(self) -> (*mut T, u64) {
+        self.inner.into_inner()
+    }
+
+    /// Loads the pointer and stamp.
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only* if `order` is [`Release`][rel] or
+    /// [`AcqRel`][acq_rel].
+    ///
+    /// [rel]: Ordering::Release
+    /// [acq_rel]: Ordering::AcqRel
+    #[inline]
+    pub fn load(&self, order: Ordering) -> (*mut T, u64) {
+        crate::debug_assert_load_ordering(order);
+        self.with_lock(|inner| *inner)
+    }
+
+    /// Stores the pointer and stamp `new`.
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only* if `order` is [`Acquire`][acq] or
+    /// [`AcqRel`][acq_rel].
+    ///
+    /// [acq]: Ordering::Acquire
+    /// [acq_rel]: Ordering::AcqRel
+    #[inline]
+    pub fn store(&self, new: (*mut T, u64), order: Ordering) {
+        crate::debug_assert_store_ordering(order);
+        self.with_lock(|inner| *inner = new);
+    }
+
+    /// Stores `new` if the current pointer **and** stamp are both equal to
+    /// `current`.
+    ///
+    /// The return value is a result indicating whether the new value was
+    /// written and containing the previous value.
+    /// On success this value is guaranteed to be equal to `current`.
+    ///
+    /// # Panics
+    ///
+    /// Panics in *debug builds only* if the pair of orderings is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::sync::atomic::Ordering;
+    ///
+    /// use tagptr::AtomicStampedPtr;
+    ///
+    /// const RELAXED: (Ordering, Ordering) = (Ordering::Relaxed, Ordering::Relaxed);
+    ///
+    /// let reference: *mut i32 = &mut 1;
+    /// let ptr = AtomicStampedPtr::new(reference, 1);
+    ///
+    /// // the pointer is equal but the stamp is not
+    /// assert_eq!(ptr.compare_exchange((reference, 0), (reference, 2), RELAXED), Err((reference, 1)));
+    /// assert_eq!(ptr.compare_exchange((reference, 1), (reference, 2), RELAXED), Ok((reference, 1)));
+    /// ```
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: (*mut T, u64),
+        new: (*mut T, u64),
+        (success, failure): (Ordering, Ordering),
+    ) -> Result<(*mut T, u64), (*mut T, u64)> {
+        crate::debug_assert_cas_orderings(success, failure);
+        self.with_lock(|inner| {
+            let prev = *inner;
+            if prev == current {
+                *inner = new;
+                Ok(prev)
+            } else {
+                Err(prev)
+            }
+        })
+    }
+
+    /// Calls `func` with exclusive access to the inner pair while holding the
+    /// lock.
+    #[inline]
+    fn with_lock<R>(&self, func: impl FnOnce(&mut (*mut T, u64)) -> R) -> R {
+        while self.lock.swap(true, Ordering::Acquire) {
+            while self.lock.load(Ordering::Relaxed) {
+                hint::spin_loop();
+            }
+        }
+
+        // SAFETY: the lock guarantees exclusive access to the inner pair
+        let res = func(unsafe { &mut *self.inner.get() });
+        self.lock.store(false, Ordering::Release);
+        res
+    }
+}
+
+/********** impl Debug ****************************************************************************/
+
+impl<T> fmt::Debug for AtomicStampedPtr<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (ptr, stamp) = self.load(Ordering::SeqCst);
+        f.debug_struct("AtomicStampedPtr").field("ptr", &ptr).field("stamp", &stamp).finish()
+    }
+}
+
+/********** impl Default **************************************************************************/
+
+impl<T> Default for AtomicStampedPtr<T> {
+    impl_default!();
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{ptr, sync::atomic::Ordering};
+
+    type AtomicStampedPtr = crate::AtomicStampedPtr<i32>;
+
+    const RELAXED: (Ordering, Ordering) = (Ordering::Relaxed, Ordering::Relaxed);
+
+    #[test]
+    fn test_compare_exchange_stamp_mismatch() {
+        let (a, b): (*mut i32, *mut i32) = (&mut 1, &mut 2);
+        let ptr = AtomicStampedPtr::new(a, u64::MAX);
+
+        // the pointer matches but the stamp does not
+        assert_eq!(ptr.compare_exchange((a, 0), (b, 1), RELAXED), Err((a, u64::MAX)));
+        // the stamp matches but the pointer does not
+        assert_eq!(ptr.compare_exchange((b, u64::MAX), (b, 1), RELAXED), Err((a, u64::MAX)));
+        assert_eq!(ptr.compare_exchange((a, u64::MAX), (b, 0), RELAXED), Ok((a, u64::MAX)));
+        assert_eq!(ptr.load(Ordering::Relaxed), (b, 0));
+    }
+
+    #[test]
+    fn test_load_store() {
+        let reference: *mut i32 = &mut 1;
+        let ptr = AtomicStampedPtr::default();
+        assert_eq!(ptr.load(Ordering::Relaxed), (ptr::null_mut(), 0));
+
+        ptr.store((reference, 1 << 63), Ordering::Relaxed);
+        assert_eq!(ptr.into_inner(), (reference, 1 << 63));
+    }
+
+    #[cfg(not(miri))]
+    #[test]
+    fn test_concurrent_stamp_increments() {
+        use std::thread;
+
+        const THREADS: u64 = 4;
+        const INCREMENTS: u64 = 1_000;
+
+        let ptr = AtomicStampedPtr::null();
+        thread::scope(|s| {
+            for _ in 0..THREADS {
+                s.spawn(|| {
+                    for _ in 0..INCREMENTS {
+                        let mut curr = ptr.load(Ordering::Relaxed);
+                        while let Err(actual) =
+                            ptr.compare_exchange(curr, (curr.0, curr.1 + 1), RELAXED)
+                        {
+                            curr = actual;
+                        }
+                    }
+                });
+            }
+        });
+
+        assert_eq!(ptr.load(Ordering::Relaxed).1, THREADS * INCREMENTS);
+    }
+}
@@ -0,0 +1,197 @@
+use core::{
+    fmt, mem,
+    ops::{Deref, DerefMut},
+};
+
+use alloc::boxed::Box;
+
+use crate::{TagNonNull, Tagged};
+
+/********** impl Send + Sync **********************************************************************/
+
+// SAFETY: `Tagged` uniquely owns its `T` like a `Box<T>`, so sending it only
+// moves that `T` to another thread
+unsafe impl<T: Send, const N: usize> Send for Tagged<T, N> {}
+// SAFETY: like for a `Box<T>`, a shared `Tagged` only ever grants shared access
+// to its `T`
+unsafe impl<T: Sync, const N: usize> Sync for Tagged<T, N> {}
+
+/********** impl inherent *************************************************************************/
+
+impl<T, const N: usize> Tagged<T, N> {
+    doc_comment! {
+        doc_tag_bits!(),
+        pub const TAG_BITS: usize = N;
+    }
+
+    doc_comment! {
+        doc_tag_mask!(),
+        pub const TAG_MASK: usize = crate::mark_mask(Self::TAG_BITS);
+    }
+
+    /// Allocates `value` on the heap and tags it with `tag`.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as
+    /// [`TagNonNull::from_box`][crate::TagNonNull::from_box].
+    #[inline]
+    pub fn new(value: T, tag: usize) -> Self {
+        Self::from_box(Box::new(value), tag)
+    }
+
+    /// Takes ownership of the `boxed` value and tags it with `tag`.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as
+    /// [`TagNonNull::from_box`][crate::TagNonNull::from_box].
+    #[inline]
+    pub fn from_box(boxed: Box<T>, tag: usize) -> Self {
+        Self { inner: TagNonNull::from_box(boxed, tag), _marker: Default::default() }
+    }
+
+    /// Consumes `self` and returns the owned [`Box`], discarding the tag value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type Tagged = tagptr::Tagged<i32, 2>;
+    ///
+    /// let tagged = Tagged::new(1, 0b11);
+    /// assert_eq!(*tagged.into_box(), 1);
+    /// ```
+    #[inline]
+    pub fn into_box(self) -> Box<T> {
+        let inner = self.inner;
+        mem::forget(self);
+        // SAFETY: the pointer was created from a box and is owned by `self`
+        unsafe { inner.into_box() }
+    }
+
+    /// Returns the tag value.
+    #[inline]
+    pub fn tag(&self) -> usize {
+        self.inner.decompose_tag()
+    }
+
+    /// Sets the tag value to `tag`, truncating any excess bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// type Tagged = tagptr::Tagged<i32, 2>;
+    ///
+    /// let mut tagged = Tagged::new(1, 0b01);
+    /// tagged.set_tag(0b110);
+    /// assert_eq!(tagged.tag(), 0b10);
+    /// ```
+    #[inline]
+    pub fn set_tag(&mut self, tag: usize) {
+        self.inner = self.inner.set_tag(tag);
+    }
+
+    /// Returns the (non-owning) marked pointer to the value, including the
+    /// tag.
+    ///
+    /// The pointer remains valid for as long as `self` is neither dropped
+    /// nor converted into a [`Box`], and must not be used for mutating the
+    /// value while `self` is borrowed.
+    #[inline]
+    pub fn as_tag_non_null(&self) -> TagNonNull<T, N> {
+        self.inner
+    }
+}
+
+/********** impl Debug ****************************************************************************/
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for Tagged<T, N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Tagged").field("value", &**self).field("tag", &self.tag()).finish()
+    }
+}
+
+/********** impl Deref ****************************************************************************/
+
+impl<T, const N: usize> Deref for Tagged<T, N> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: the pointer is owned by `self` and valid for its lifetime
+        unsafe { self.inner.as_ref() }
+    }
+}
+
+/********** impl DerefMut *************************************************************************/
+
+impl<T, const N: usize> DerefMut for Tagged<T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: the pointer is owned by `self` and valid for its lifetime
+        unsafe { self.inner.as_mut() }
+    }
+}
+
+/********** impl Drop *****************************************************************************/
+
+impl<T, const N: usize> Drop for Tagged<T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: the pointer was created from a box and is owned by `self`
+        mem::drop(unsafe { self.inner.into_box() });
+    }
+}
+
+/********** impl From (Box<T>) ********************************************************************/
+
+impl<T, const N: usize> From<Box<T>> for Tagged<T, N> {
+    #[inline]
+    fn from(boxed: Box<T>) -> Self {
+        Self::from_box(boxed, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+    use std::boxed::Box;
+
+    type Tagged = crate::Tagged<i32, 2>;
+
+    #[test]
+    fn test_deref_mut_and_tag() {
+        let mut tagged = Tagged::new(1, 0b01);
+        *tagged += 1;
+        tagged.set_tag(0b10);
+        *tagged *= 2;
+
+        assert_eq!((*tagged, tagged.tag()), (4, 0b10));
+        assert_eq!(unsafe { tagged.as_tag_non_null().decompose_ref() }, (&4, 0b10));
+        assert_eq!(*tagged.into_box(), 4);
+    }
+
+    #[test]
+    fn test_drop() {
+        struct DropCount<'a>(&'a Cell<usize>);
+
+        impl Drop for DropCount<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let count = Cell::new(0);
+        let tagged = crate::Tagged::<_, 2>::from(Box::new(DropCount(&count)));
+        assert_eq!(tagged.tag(), 0);
+        drop(tagged);
+        assert_eq!(count.get(), 1);
+
+        let tagged = crate::Tagged::<_, 2>::new(DropCount(&count), 0b11);
+        let boxed = tagged.into_box();
+        assert_eq!(count.get(), 1);
+        drop(boxed);
+        assert_eq!(count.get(), 2);
+    }
+}
@@ -32,6 +32,38 @@
 //! The resulting type would consider the first actual bit of the pointer to be
 //! part of its tag and return a potentially corrupted pointer in methods such
 //! as [`decompose`][TagPtr::decompose].
+//! Composing such a type from a raw pointer (e.g., through
+//! [`compose`][TagPtr::compose]) is therefore rejected at compile time:
+//!
+//! ```compile_fail
+//! // a pointer to a `u16` has only 1 unused bit
+//! type TagPtr = tagptr::TagPtr<u16, 4>;
+//!
+//! let ptr = TagPtr::compose(&mut 1, 0b1);
+//! ```
+//!
+//! This applies to [`compose`][TagPtr::compose] and every constructor built
+//! on it (e.g., [`with_ptr`][TagPtr::with_ptr],
+//! [`from_ref_tagged`][TagPtr::from_ref_tagged] or [`TagNonNull::compose`]).
+//! The exceptions are methods which only replace the tag of an existing
+//! pointer, [`from_fn_ptr`][TagPtr::from_fn_ptr] and
+//! [`try_compose`][TagPtr::try_compose], which checks the actual address at
+//! runtime instead.
+//! Hence, `compose` rejects under-aligned or zero-sized `T` (e.g., in
+//! `TagPtr<(), 2>`) at compile time; use `try_compose` or compose a
+//! well-formed tag pointer and [`cast`][TagPtr::cast] it instead:
+//!
+//! ```
+//! type TagPtr = tagptr::TagPtr<(), 2>;
+//!
+//! let erased = (&mut 1u32 as *mut u32).cast::<()>();
+//! let ptr = TagPtr::try_compose(erased, 0b11).unwrap();
+//! assert_eq!(ptr.decompose(), (erased, 0b11));
+//!
+//! let ptr: TagPtr = tagptr::TagPtr::<u32, 2>::compose(&mut 1, 0b11).cast();
+//! assert_eq!(ptr.decompose_tag(), 0b11);
+//! ```
+//!
 //! The [`has_sufficient_alignment`] and [`assert_alignment`] functions can be
 //! used to explicitly check for or assert this property.
 //! There is, however, one exception where using an otherwise ill-formed tag
@@ -41,6 +73,30 @@
 //! and the same number of tag bits such as `TagPtr<(), 3>` for the purpose of
 //! type-erasure.
 //!
+//! # Features
+//!
+//! - `alloc`: enables conversions between owned `Box` allocations and tagged
+//!   pointers, as well as the owning `Tagged` type.
+//! - `bitflags`: enables composing and decomposing [`TagPtr`]s with tag values
+//!   modelled as flags types generated by the `bitflags` crate.
+//! - `bytemuck`: implements `Zeroable` and `Pod` for [`TagPtr`].
+//! - `loom` (`cfg`, not a feature): building with `RUSTFLAGS="--cfg loom"`
+//!   backs [`AtomicTagPtr`] with `loom`'s `AtomicPtr` (and uses its `fence`)
+//!   instead of the ones from `core`, so that concurrent algorithms built on
+//!   top of it can be model-checked.
+//!   The `const` constructors and [`get_mut`][AtomicTagPtr::get_mut] are not
+//!   available in this configuration.
+//! - `no-panic`: compiles out all debug assertions (e.g., for misaligned
+//!   pointers, tag bit indices or memory orderings), even in debug builds.
+//!   Any validation must then be done explicitly through the fallible
+//!   `try_*` methods instead.
+//!   Note, that the atomic operations of `core` still panic for invalid
+//!   memory orderings.
+//! - `std`: implements `std::error::Error` for the crate's error types
+//!   ([`Null`], [`MisalignedError`] and [`ComposeError`]), implies `alloc`.
+//! - `serde`: implements `Serialize` and `Deserialize` for [`TagPtr`] as a
+//!   tuple of its (tag-stripped) address and tag value.
+//!
 //! # Example
 //!
 //! Storing a boolean status flag alongside the pointer to a mutable `u64`:
@@ -59,19 +115,69 @@
 
 #![no_std]
 
-#[cfg(test)]
+#[cfg(any(test, feature = "std"))]
 extern crate std;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[macro_use]
 mod macros;
 
 mod imp {
     mod atomic;
+    mod based;
+    mod dyn_ptr;
+    mod fat;
+    mod high;
     mod non_null;
+    mod option;
     mod ptr;
+    mod stamped;
+    #[cfg(feature = "alloc")]
+    mod tagged;
 }
 
-use core::{marker::PhantomData, mem, ptr::NonNull, sync::atomic::AtomicUsize};
+pub mod raw;
+
+use core::{
+    cell::UnsafeCell,
+    fmt,
+    marker::PhantomData,
+    mem,
+    ptr::NonNull,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+#[cfg(not(loom))]
+use core::sync::atomic::{fence, AtomicPtr};
+#[cfg(loom)]
+use loom::sync::atomic::{fence, AtomicPtr};
+
+// *************************************************************************************************
+// AtomicStampedPtr (impl in "imp/stamped.rs")
+// *************************************************************************************************
+
+/// A raw pointer type which can be safely shared between threads and which is
+/// paired with a separate 64-bit *stamp*, e.g., for ABA prevention.
+///
+/// Unlike for [`AtomicTagPtr`], the stamp does not occupy any bits of the
+/// pointer, so neither the range of the stamp nor the pointer's alignment are
+/// restricted.
+/// All operations act on the `(ptr, stamp)` pair as a whole.
+///
+/// Since double-word atomic operations are not available on stable Rust, all
+/// operations are serialized by an internal spin lock, so this type is **not**
+/// lock-free.
+/// This is the case even for targets supporting 128-bit atomics (i.e., with
+/// `cfg(target_has_atomic = "128")`), as `AtomicU128` is still unstable.
+/// Consequently, all operations are at least as strong as
+/// [`Acquire`][Ordering::Acquire] (loads) or [`Release`][Ordering::Release]
+/// (stores), regardless of the given memory orderings.
+pub struct AtomicStampedPtr<T> {
+    lock: AtomicBool,
+    inner: UnsafeCell<(*mut T, u64)>,
+}
 
 // *************************************************************************************************
 // AtomicTagPtr (impl in "imp/atomic.rs")
@@ -89,10 +195,226 @@ use core::{marker::PhantomData, mem, ptr::NonNull, sync::atomic::AtomicUsize};
 /// [atomic]: core::sync::atomic::AtomicPtr
 #[repr(transparent)]
 pub struct AtomicTagPtr<T, const N: usize> {
-    inner: AtomicUsize,
+    inner: AtomicPtr<T>,
+}
+
+// *************************************************************************************************
+// BasedTagPtr (impl in "imp/based.rs")
+// *************************************************************************************************
+
+/// A tagged pointer type, which stores an offset relative to some base address
+/// instead of an absolute address and which can use up to `N` of its lower
+/// bits to store additional information (the *tag*).
+///
+/// The base address is not stored but has to be supplied whenever the pointer
+/// is resolved, which allows using this type in relocatable memory regions
+/// (e.g., shared memory mapped at different addresses in different
+/// processes), in which absolute pointers are not stable.
+/// The base address must be aligned at least as strictly as `T`, otherwise
+/// offsets to well-aligned instances could not be tagged.
+/// See the [crate] level documentation for restrictions on the value of `N`.
+#[repr(transparent)]
+pub struct BasedTagPtr<T, const N: usize> {
+    inner: usize,
     _marker: PhantomData<*mut T>,
 }
 
+// *************************************************************************************************
+// ByPtr (impl in "imp/ptr.rs")
+// *************************************************************************************************
+
+/// A wrapper for a [`TagPtr`], which compares, orders and hashes it by its
+/// pointer bits only, ignoring its tag value.
+///
+/// This allows using marked pointers as keys in maps or sets, which treat
+/// all marked pointers to the same address as identical.
+/// The comparison traits of [`TagPtr`] itself always consider the entire
+/// (marked) pointer value.
+///
+/// # Examples
+///
+/// ```
+/// use tagptr::ByPtr;
+///
+/// type TagPtr = tagptr::TagPtr<i32, 2>;
+///
+/// let reference = &mut 1;
+/// let (a, b) = (TagPtr::compose(reference, 0b01), TagPtr::compose(reference, 0b10));
+///
+/// assert_ne!(a, b);
+/// assert_eq!(ByPtr(a), ByPtr(b));
+/// ```
+#[repr(transparent)]
+pub struct ByPtr<T, const N: usize>(pub TagPtr<T, N>);
+
+// *************************************************************************************************
+// DecomposedNonNull (impl in "imp/non_null.rs")
+// *************************************************************************************************
+
+/// The separated parts of a decomposed [`TagNonNull`] with named fields.
+pub struct DecomposedNonNull<T> {
+    /// The non-null pointer stripped of its tag.
+    pub ptr: NonNull<T>,
+    /// The pointer's tag value.
+    pub tag: usize,
+}
+
+// *************************************************************************************************
+// DecomposedPtr (impl in "imp/ptr.rs")
+// *************************************************************************************************
+
+/// The separated parts of a decomposed [`TagPtr`] with named fields.
+pub struct DecomposedPtr<T> {
+    /// The raw pointer stripped of its tag.
+    pub ptr: *mut T,
+    /// The pointer's tag value.
+    pub tag: usize,
+}
+
+// *************************************************************************************************
+// DynTagPtr (impl in "imp/dyn_ptr.rs")
+// *************************************************************************************************
+
+/// A raw pointer type which can use a number of its lower bits determined at
+/// runtime to store additional information (the *tag*).
+///
+/// This is the runtime equivalent of [`TagPtr`] for cases in which the
+/// number of used tag bits is not known at compile time.
+/// The number of tag bits is stored alongside the pointer, so this type is
+/// larger than a single pointer.
+/// The same restrictions as for the value of `N` in [`TagPtr`] apply to the
+/// number of tag bits, i.e., it must not exceed the number of lower bits
+/// guaranteed to be unused by the alignment of `T`, which is checked when
+/// constructing a pointer.
+pub struct DynTagPtr<T> {
+    inner: *mut T,
+    bits: u8,
+}
+
+// *************************************************************************************************
+// FatTagPtr (impl in "imp/fat.rs")
+// *************************************************************************************************
+
+/// A raw, unsafe pointer type like `*mut T` for potentially unsized types
+/// (e.g., slices or trait objects), which can use up to `N` of the lower bits
+/// of its data pointer to store additional information (the *tag*).
+///
+/// Only the data pointer half of a fat pointer is tagged, its metadata (e.g.,
+/// the length of a slice or the vtable of a trait object) is passed through
+/// unchanged.
+/// Since the alignment of an unsized type is in general not known statically,
+/// it is the user's responsibility to ensure that all composed data pointers
+/// have at least `N` unused lower bits.
+pub struct FatTagPtr<T: ?Sized, const N: usize> {
+    inner: *mut T,
+    _marker: PhantomData<()>,
+}
+
+// *************************************************************************************************
+// HighTagPtr (impl in "imp/high.rs")
+// *************************************************************************************************
+
+/// A raw, unsafe pointer type like `*mut T` which uses `N` of its *upper* bits
+/// to store additional information (the *tag*).
+///
+/// Unlike for [`TagPtr`], the number of tag bits does not depend on the
+/// alignment of `T`, but on the number of upper pointer bits which are unused
+/// by the target platform.
+/// On x86-64, e.g., (canonical) addresses use only 48 (or 57 with 5-level
+/// paging) bits, so up to 16 (or 7) upper bits are available, whereas AArch64
+/// with the *top byte ignore* (TBI) feature guarantees 8 available bits.
+/// It is the user's responsibility to ensure that the chosen `N` is valid for
+/// the target platform, which is generally not the case for 32-bit targets.
+/// Only values of `N` less than the number of pointer bits are accepted at
+/// all, which is checked at compile time:
+///
+/// ```compile_fail
+/// let _ = tagptr::HighTagPtr::<i32, { usize::BITS as usize }>::null();
+/// ```
+///
+/// Decomposing the pointer restores the canonical form of the address by
+/// sign-extending it from the highest pointer bit.
+/// A marked pointer must never be dereferenced without decomposing it first,
+/// even on platforms which ignore some of the upper address bits.
+#[repr(transparent)]
+pub struct HighTagPtr<T, const N: usize> {
+    inner: *mut T,
+    _marker: PhantomData<()>,
+}
+
+// *************************************************************************************************
+// SeqGuard (impl in "imp/atomic.rs")
+// *************************************************************************************************
+
+/// A guard for optimistically reading data protected by a sequence counter,
+/// which is stored in the tag bits of an [`AtomicTagPtr`].
+///
+/// This implements the reader side of a *seqlock*: Writers increment the
+/// sequence counter to an odd value before and to an even value after
+/// modifying the protected data, so readers can detect whether their reads
+/// may have been interleaved with a concurrent write and retry.
+///
+/// # Examples
+///
+/// ```
+/// use core::sync::atomic::{AtomicUsize, Ordering};
+///
+/// type AtomicTagPtr = tagptr::AtomicTagPtr<u64, 3>;
+///
+/// let seq = AtomicTagPtr::null();
+/// let data = AtomicUsize::new(1);
+///
+/// let value = loop {
+///     let guard = seq.seq_guard();
+///     if guard.is_writing() {
+///         continue;
+///     }
+///
+///     let value = data.load(Ordering::Relaxed);
+///     if guard.validate() {
+///         break value;
+///     }
+/// };
+///
+/// assert_eq!(value, 1);
+/// ```
+pub struct SeqGuard<'a, T, const N: usize> {
+    atomic: &'a AtomicTagPtr<T, N>,
+    snapshot: TagPtr<T, N>,
+}
+
+// *************************************************************************************************
+// TagOption (impl in "imp/option.rs")
+// *************************************************************************************************
+
+/// A (nullable) marked pointer, which distinguishes `null` and non-null
+/// pointers by its variants while preserving the tag value in either case.
+///
+/// This is the safe equivalent of branching on
+/// [`is_null`][TagPtr::is_null] for a [`TagPtr`].
+///
+/// # Examples
+///
+/// ```
+/// use core::ptr;
+///
+/// use tagptr::TagOption;
+///
+/// type TagPtr = tagptr::TagPtr<i32, 2>;
+///
+/// let ptr = TagPtr::compose(ptr::null_mut(), 0b10);
+/// match TagOption::from_marked_ptr(ptr) {
+///     TagOption::Value(_) => unreachable!(),
+///     TagOption::Null(tag) => assert_eq!(tag, 0b10),
+/// }
+/// ```
+pub enum TagOption<T, const N: usize> {
+    /// A non-null marked pointer.
+    Value(TagNonNull<T, N>),
+    /// A `null` pointer with the contained tag value.
+    Null(usize),
+}
+
 // *************************************************************************************************
 // TagPtr (impl in "imp/ptr.rs")
 // *************************************************************************************************
@@ -137,6 +459,36 @@ pub struct TagNonNull<T, const N: usize> {
     _marker: PhantomData<()>,
 }
 
+// *************************************************************************************************
+// Tagged (impl in "imp/tagged.rs")
+// *************************************************************************************************
+
+/// An owned, heap-allocated value of type `T`, which is referenced through a
+/// [`TagNonNull`] and hence carries a tag value alongside it.
+///
+/// This is equivalent to a [`Box<T>`][alloc::boxed::Box] with additional
+/// `N` tag bits, which can be safely accessed and modified.
+/// The value can be accessed through [`Deref`][core::ops::Deref] and
+/// [`DerefMut`][core::ops::DerefMut] and is dropped when the `Tagged` is
+/// dropped.
+///
+/// # Examples
+///
+/// ```
+/// type Tagged = tagptr::Tagged<i32, 2>;
+///
+/// let mut tagged = Tagged::new(1, 0b01);
+/// *tagged += 1;
+/// tagged.set_tag(0b10);
+///
+/// assert_eq!((*tagged, tagged.tag()), (2, 0b10));
+/// ```
+#[cfg(feature = "alloc")]
+pub struct Tagged<T, const N: usize> {
+    inner: TagNonNull<T, N>,
+    _marker: PhantomData<T>,
+}
+
 // *************************************************************************************************
 // Null
 // *************************************************************************************************
@@ -158,6 +510,123 @@ impl Null {
     }
 }
 
+/********** impl Display **************************************************************************/
+
+impl fmt::Display for Null {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "marked pointer is null (tag: {:#b})", self.0)
+    }
+}
+
+/********** impl Error ****************************************************************************/
+
+#[cfg(feature = "std")]
+impl std::error::Error for Null {}
+
+// *************************************************************************************************
+// MisalignedError
+// *************************************************************************************************
+
+/// A type representing a pointer that is not sufficiently aligned for its
+/// pointed-to type.
+///
+/// The contained `usize` is the (tag-stripped) address of the pointer.
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, Ord, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct MisalignedError(pub usize);
+
+/********** impl inherent *************************************************************************/
+
+impl MisalignedError {
+    /// Returns the misaligned address.
+    #[inline]
+    pub fn addr(self) -> usize {
+        self.0
+    }
+}
+
+/********** impl Display **************************************************************************/
+
+impl fmt::Display for MisalignedError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "pointer is misaligned (address: {:#x})", self.0)
+    }
+}
+
+/********** impl Error ****************************************************************************/
+
+#[cfg(feature = "std")]
+impl std::error::Error for MisalignedError {}
+
+// *************************************************************************************************
+// ComposeError
+// *************************************************************************************************
+
+/// An error returned when a pointer and tag value can not be composed into a
+/// marked pointer without loss of information.
+#[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
+pub enum ComposeError {
+    /// The pointer's lower bits are not all zero, i.e., it is not sufficiently
+    /// aligned for the requested number of tag bits.
+    ///
+    /// The contained `usize` is the address of the pointer.
+    Unaligned(usize),
+    /// The tag value exceeds the maximum value representable by the tag bits.
+    ///
+    /// The contained `usize` is the (untruncated) tag value.
+    TagOverflow(usize),
+}
+
+/********** impl Display **************************************************************************/
+
+impl fmt::Display for ComposeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ComposeError::Unaligned(addr) => {
+                write!(f, "pointer has tag bits set (address: {:#x})", addr)
+            }
+            ComposeError::TagOverflow(tag) => {
+                write!(f, "tag value exceeds the available tag bits (tag: {:#b})", tag)
+            }
+        }
+    }
+}
+
+/********** impl Error ****************************************************************************/
+
+#[cfg(feature = "std")]
+impl std::error::Error for ComposeError {}
+
+// *************************************************************************************************
+// LogRepr
+// *************************************************************************************************
+
+/// A plain representation of a marked pointer's address and tag value
+/// intended for logging and telemetry.
+///
+/// Since this type does not contain any actual pointer, it is trivially
+/// [`Send`] and [`Sync`] and can be safely passed on (e.g., to a logging
+/// thread) even if the pointer it was created from is dangling.
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, Ord, PartialEq, PartialOrd)]
+pub struct LogRepr {
+    /// The (tag-stripped) address of the pointer.
+    pub addr: usize,
+    /// The pointer's tag value.
+    pub tag: usize,
+}
+
+/********** impl Display **************************************************************************/
+
+impl fmt::Display for LogRepr {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:#x}@{:#b}", self.addr, self.tag)
+    }
+}
+
 /********** public functions **********************************************************************/
 
 /// Returns `true` if the alignment of `T` is large enough so a pointer to an
@@ -167,6 +636,48 @@ pub const fn has_sufficient_alignment<T>(tag_bits: usize) -> bool {
     lower_bits::<T>() >= tag_bits
 }
 
+/// Returns the maximum number of tag bits a (well-aligned) pointer to an
+/// instance of `T` can store, which is determined by the alignment of `T`.
+///
+/// See also the [`max_tag_ptr`] macro.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(tagptr::max_tag_bits::<u8>(), 0);
+/// assert_eq!(tagptr::max_tag_bits::<u16>(), 1);
+/// assert_eq!(tagptr::max_tag_bits::<u64>(), 3);
+/// ```
+#[inline]
+pub const fn max_tag_bits<T>() -> usize {
+    lower_bits::<T>()
+}
+
+/// Returns the number of unused lower bits that remain in a pointer with the
+/// (non-zero) alignment `align` after reserving `tag_bits` bits for a tag, or
+/// `None` if the alignment is insufficient for storing `tag_bits` bits.
+///
+/// # Examples
+///
+/// ```
+/// const REMAINING: Option<usize> = tagptr::remaining_bits(8, 2);
+/// assert_eq!(REMAINING, Some(1));
+///
+/// assert_eq!(tagptr::remaining_bits(8, 3), Some(0));
+/// assert_eq!(tagptr::remaining_bits(8, 4), None);
+/// assert_eq!(tagptr::remaining_bits(1, 0), Some(0));
+/// assert_eq!(tagptr::remaining_bits(4096, 5), Some(7));
+/// ```
+#[inline]
+pub const fn remaining_bits(align: usize, tag_bits: usize) -> Option<usize> {
+    let available = align.trailing_zeros() as usize;
+    if tag_bits <= available {
+        Some(available - tag_bits)
+    } else {
+        None
+    }
+}
+
 /// Asserts that the alignment of `U` is large enough so a pointer to an
 /// instance may store `N` tag bits.
 ///
@@ -182,6 +693,74 @@ pub fn assert_alignment<T, const N: usize>() {
     );
 }
 
+/// Sets the tag bit at index `bit` for every atomic marked pointer in `slice`.
+///
+/// Each element is updated individually using
+/// [`fetch_or`][AtomicTagPtr::fetch_or] with the given `order`, so the
+/// operation as a whole is **not** atomic.
+///
+/// # Examples
+///
+/// ```
+/// use core::sync::atomic::Ordering;
+///
+/// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+///
+/// let nodes = [AtomicTagPtr::null(), AtomicTagPtr::null(), AtomicTagPtr::null()];
+/// tagptr::mark_all(&nodes, 1, Ordering::Relaxed);
+///
+/// for node in &nodes {
+///     assert_eq!(node.load(Ordering::Relaxed).decompose_tag(), 0b10);
+/// }
+/// ```
+#[inline]
+pub fn mark_all<T, const N: usize>(slice: &[AtomicTagPtr<T, N>], bit: u32, order: Ordering) {
+    debug_check!((bit as usize) < N, "`bit` exceeds tag bits");
+    for atomic in slice {
+        atomic.fetch_or(1 << bit, order);
+    }
+}
+
+/// Exchanges the values of the atomic marked pointers `a` and `b`.
+///
+/// The value of `a` is first loaded and swapped into `b`, after which the
+/// previous value of `b` is stored into `a`.
+/// Note that this is **not** a single atomic operation across both cells,
+/// which is impossible to achieve without additional synchronization:
+/// Other threads may observe an intermediate state, in which both cells
+/// contain the same value, and any concurrent modification of `a` is
+/// overwritten.
+///
+/// `swap_cells` takes an [`Ordering`] argument which describes the memory
+/// ordering of both swap operations, the initial load of `a` uses the
+/// strongest permissible load ordering derived from `order`.
+///
+/// # Examples
+///
+/// ```
+/// use core::sync::atomic::Ordering;
+///
+/// type AtomicTagPtr = tagptr::AtomicTagPtr<i32, 2>;
+/// type TagPtr = tagptr::TagPtr<i32, 2>;
+///
+/// let (x, y) = (&mut 1, &mut 2);
+/// let a = AtomicTagPtr::new(TagPtr::compose(x, 0b01));
+/// let b = AtomicTagPtr::new(TagPtr::compose(y, 0b10));
+///
+/// tagptr::swap_cells(&a, &b, Ordering::AcqRel);
+/// assert_eq!(a.load(Ordering::Relaxed), TagPtr::compose(y, 0b10));
+/// assert_eq!(b.load(Ordering::Relaxed), TagPtr::compose(x, 0b01));
+/// ```
+#[inline]
+pub fn swap_cells<T, const N: usize>(
+    a: &AtomicTagPtr<T, N>,
+    b: &AtomicTagPtr<T, N>,
+    order: Ordering,
+) {
+    let prev_b = b.swap(a.load(load_ordering(order)), order);
+    a.swap(prev_b, order);
+}
+
 /********** helper functions **********************************************************************/
 
 /// Composes the given `ptr` with `tag` and returns the composed marked pointer
@@ -193,21 +772,21 @@ pub fn assert_alignment<T, const N: usize>() {
 /// contains any bits in its lower bits reserved for the tag value.
 #[inline(always)]
 fn compose<T, const N: usize>(ptr: *mut T, tag: usize) -> *mut T {
-    debug_assert_eq!(ptr as usize & mark_mask(N), 0, "tag bits in raw pointer must be zeroed");
-    ((ptr as usize) | (mark_mask(N) & tag)) as *mut _
+    debug_check_eq!(ptr.addr() & mark_mask(N), 0, "tag bits in raw pointer must be zeroed");
+    ptr.map_addr(|addr| addr | (mark_mask(N) & tag))
 }
 
-/// Decomposes the integer representation of a `ptr` for a given number
-/// of `tag_bits` into only a raw pointer stripped of its tag.
+/// Decomposes a (marked) `ptr` for a given number of `tag_bits` into only a
+/// raw pointer stripped of its tag, preserving its provenance.
 #[inline(always)]
-const fn decompose_ptr<T>(ptr: usize, tag_bits: usize) -> *mut T {
-    (ptr & !mark_mask(tag_bits)) as *mut _
+fn decompose_ptr<T>(ptr: *mut T, tag_bits: usize) -> *mut T {
+    ptr.map_addr(|addr| addr & !mark_mask(tag_bits))
 }
 
 /// Decomposes the integer representation of a `ptr` for a given number
 /// of `tag_bits` into only a separated tag value.
 #[inline(always)]
-const fn decompose_tag<T>(ptr: usize, tag_bits: usize) -> usize {
+const fn decompose_tag(ptr: usize, tag_bits: usize) -> usize {
     ptr & mark_mask(tag_bits)
 }
 
@@ -223,3 +802,55 @@ const fn lower_bits<T>() -> usize {
 const fn mark_mask(tag_bits: usize) -> usize {
     (1 << tag_bits) - 1
 }
+
+/// Returns the strongest ordering permissible for a load, which is implied by
+/// the (read-modify-write) ordering `order`.
+#[inline(always)]
+const fn load_ordering(order: Ordering) -> Ordering {
+    match order {
+        Ordering::Release => Ordering::Relaxed,
+        Ordering::AcqRel => Ordering::Acquire,
+        order => order,
+    }
+}
+
+/// Asserts (in debug builds only) that `order` is valid for loads.
+#[inline(always)]
+#[cfg_attr(feature = "no-panic", allow(unused_variables))]
+fn debug_assert_load_ordering(order: Ordering) {
+    debug_check!(
+        !matches!(order, Ordering::Release | Ordering::AcqRel),
+        "there is no such thing as a release load"
+    );
+}
+
+/// Asserts (in debug builds only) that `order` is valid for stores.
+#[inline(always)]
+#[cfg_attr(feature = "no-panic", allow(unused_variables))]
+fn debug_assert_store_ordering(order: Ordering) {
+    debug_check!(
+        !matches!(order, Ordering::Acquire | Ordering::AcqRel),
+        "there is no such thing as an acquire store"
+    );
+}
+
+/// Asserts (in debug builds only) that `success` and `failure` are a valid
+/// pair of orderings for compare-exchange operations.
+#[inline(always)]
+#[cfg_attr(feature = "no-panic", allow(unused_variables))]
+fn debug_assert_cas_orderings(success: Ordering, failure: Ordering) {
+    debug_check!(
+        !matches!(failure, Ordering::Release | Ordering::AcqRel),
+        "there is no such thing as a release failure ordering"
+    );
+    debug_check!(
+        match failure {
+            Ordering::Acquire => {
+                matches!(success, Ordering::Acquire | Ordering::AcqRel | Ordering::SeqCst)
+            }
+            Ordering::SeqCst => matches!(success, Ordering::SeqCst),
+            _ => true,
+        },
+        "a failure ordering can't be stronger than a success ordering"
+    );
+}
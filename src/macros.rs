@@ -1,11 +1,58 @@
 #[macro_use]
 mod doc;
 
+/// Expands to the [`TagPtr`][crate::TagPtr] type for the given (concrete)
+/// type with the maximum number of tag bits permitted by its alignment.
+///
+/// # Examples
+///
+/// ```
+/// type TagPtr = tagptr::max_tag_ptr!(u64);
+/// assert_eq!(TagPtr::TAG_BITS, 3);
+///
+/// #[repr(align(64))]
+/// struct Aligned64(u8);
+/// assert_eq!(<tagptr::max_tag_ptr!(Aligned64)>::TAG_BITS, 6);
+/// ```
+#[macro_export]
+macro_rules! max_tag_ptr {
+    ($ty:ty) => {
+        $crate::TagPtr<$ty, { $crate::max_tag_bits::<$ty>() }>
+    };
+}
+
+/// A [`debug_assert!`] which is compiled out entirely if the `no-panic`
+/// feature is enabled.
+macro_rules! debug_check {
+    ($($arg:tt)*) => {
+        #[cfg(not(feature = "no-panic"))]
+        debug_assert!($($arg)*);
+    };
+}
+
+/// A [`debug_assert_eq!`] which is compiled out entirely if the `no-panic`
+/// feature is enabled.
+macro_rules! debug_check_eq {
+    ($($arg:tt)*) => {
+        #[cfg(not(feature = "no-panic"))]
+        debug_assert_eq!($($arg)*);
+    };
+}
+
+/// A [`debug_assert_ne!`] which is compiled out entirely if the `no-panic`
+/// feature is enabled.
+macro_rules! debug_check_ne {
+    ($($arg:tt)*) => {
+        #[cfg(not(feature = "no-panic"))]
+        debug_assert_ne!($($arg)*);
+    };
+}
+
 macro_rules! impl_clone {
     () => {
         #[inline]
         fn clone(&self) -> Self {
-            Self { inner: self.inner, _marker: PhantomData }
+            *self
         }
     };
 }
@@ -20,6 +67,16 @@ macro_rules! impl_debug {
     };
 }
 
+macro_rules! impl_display {
+    () => {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let (ptr, tag) = self.decompose();
+            write!(f, "{:p}@{:#0width$b}", ptr, tag, width = Self::TAG_BITS + 2)
+        }
+    };
+}
+
 macro_rules! impl_default {
     () => {
         #[inline]
@@ -51,7 +108,7 @@ macro_rules! impl_partial_ord {
     () => {
         #[inline]
         fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-            self.inner.partial_cmp(&other.inner)
+            Some(self.cmp(other))
         }
     };
 }
@@ -1,4 +1,4 @@
-/// All macros for generating documentation.
+//! All macros for generating documentation.
 
 /// A macro for generating arbitrary documented code items
 macro_rules! doc_comment {
@@ -77,7 +77,9 @@ macro_rules! doc_compose {
         "Composes a new marked pointer from a raw `ptr` and a `tag` value.\n\n\
         The supplied `ptr` is assumed to be well-aligned (i.e. has no tag bits \
         set) and calling this function may lead to unexpected results when \
-        this is not the case."
+        this is not the case.\n\n\
+        In debug builds (unless the `no-panic` feature is enabled), this is \
+        asserted and a mis-aligned `ptr` causes a panic."
     };
 }
 
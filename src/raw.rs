@@ -0,0 +1,85 @@
+//! Free functions for composing and decomposing raw `*mut T` pointers, which
+//! underlie all of this crate's marked pointer types.
+//!
+//! These are intended for building custom marked pointer types on top of this
+//! crate's primitives.
+//! As with [`TagPtr`][crate::TagPtr], the number of tag bits `N` is given
+//! explicitly and the tag value is always stored in the `N` lower bits of the
+//! pointer.
+
+/// Returns the bit-mask for the `N` lower bits containing the tag value.
+///
+/// # Examples
+///
+/// ```
+/// use tagptr::raw;
+///
+/// assert_eq!(raw::mark_mask::<0>(), 0);
+/// assert_eq!(raw::mark_mask::<3>(), 0b111);
+/// ```
+#[inline]
+pub const fn mark_mask<const N: usize>() -> usize {
+    crate::mark_mask(N)
+}
+
+/// Composes the given `ptr` with `tag` and returns the composed marked pointer
+/// as a raw `*mut T`, preserving the provenance of `ptr`.
+///
+/// Any `tag` bits above the `N` lower bits are silently truncated.
+/// The supplied `ptr` is assumed to be well-aligned (i.e. has no tag bits
+/// set), otherwise its lower bits are combined with the `tag` value.
+///
+/// # Panics
+///
+/// Panics in *debug builds only* (unless the `no-panic` feature is enabled)
+/// if `ptr` has any of its `N` lower bits set.
+///
+/// # Examples
+///
+/// ```
+/// use tagptr::raw;
+///
+/// let reference = &mut 1u64;
+/// let ptr = raw::compose::<_, 3>(reference, 0b1101);
+/// assert_eq!(ptr as usize, reference as *mut _ as usize | 0b101);
+/// ```
+#[inline]
+pub fn compose<T, const N: usize>(ptr: *mut T, tag: usize) -> *mut T {
+    crate::compose::<T, N>(ptr, tag)
+}
+
+/// Decomposes a (marked) `ptr` into only the raw pointer with its `N` lower
+/// bits cleared, preserving its provenance.
+///
+/// # Examples
+///
+/// ```
+/// use tagptr::raw;
+///
+/// let reference = &mut 1u64;
+/// let ptr = raw::compose::<_, 3>(reference, 0b101);
+/// assert_eq!(raw::decompose_ptr::<_, 3>(ptr), reference as *mut _);
+/// ```
+#[inline]
+pub fn decompose_ptr<T, const N: usize>(ptr: *mut T) -> *mut T {
+    crate::decompose_ptr(ptr, N)
+}
+
+/// Decomposes a (marked) `ptr` into only the tag value stored in its `N` lower
+/// bits.
+///
+/// # Examples
+///
+/// ```
+/// use tagptr::raw;
+///
+/// let reference = &mut 1u64;
+/// let ptr = raw::compose::<_, 3>(reference, 0b101);
+/// assert_eq!(raw::decompose_tag::<_, 3>(ptr), 0b101);
+/// // decomposing with fewer bits only considers the lowest tag bits
+/// assert_eq!(raw::decompose_tag::<_, 2>(ptr), 0b01);
+/// ```
+#[inline]
+pub fn decompose_tag<T, const N: usize>(ptr: *mut T) -> usize {
+    crate::decompose_tag(ptr.addr(), N)
+}
@@ -0,0 +1,89 @@
+//! Model-checks the atomic operations of [`AtomicTagPtr`] with `loom`.
+//!
+//! Run with `RUSTFLAGS="--cfg loom" cargo test --release --test loom`.
+
+#![cfg(loom)]
+
+use core::sync::atomic::Ordering;
+
+use loom::{
+    sync::{
+        atomic::{fence, AtomicUsize},
+        Arc,
+    },
+    thread,
+};
+
+type AtomicTagPtr = tagptr::AtomicTagPtr<u64, 2>;
+type TagPtr = tagptr::TagPtr<u64, 2>;
+
+const ACQ_REL: (Ordering, Ordering) = (Ordering::AcqRel, Ordering::Acquire);
+
+#[test]
+fn compare_exchange_tag_race() {
+    loom::model(|| {
+        let ptr = Arc::new(AtomicTagPtr::null());
+
+        let handles: Vec<_> = (1..=2)
+            .map(|tag| {
+                let ptr = Arc::clone(&ptr);
+                thread::spawn(move || {
+                    let res = ptr.compare_exchange(
+                        TagPtr::null(),
+                        TagPtr::compose(core::ptr::null_mut(), tag),
+                        ACQ_REL,
+                    );
+                    res.is_ok()
+                })
+            })
+            .collect();
+
+        let wins =
+            handles.into_iter().map(|handle| handle.join().unwrap()).filter(|&ok| ok).count();
+        assert_eq!(wins, 1);
+
+        let tag = ptr.load(Ordering::Relaxed).decompose_tag();
+        assert!(tag == 0b01 || tag == 0b10);
+    });
+}
+
+#[test]
+fn fetch_or_tag_is_atomic() {
+    loom::model(|| {
+        let ptr = Arc::new(AtomicTagPtr::null());
+
+        let other = Arc::clone(&ptr);
+        let handle = thread::spawn(move || other.fetch_or_tag(0b01, Ordering::Relaxed));
+        ptr.fetch_or_tag(0b10, Ordering::Relaxed);
+        handle.join().unwrap();
+
+        assert_eq!(ptr.load(Ordering::Relaxed).decompose_tag(), 0b11);
+    });
+}
+
+#[test]
+fn seq_guard_detects_concurrent_write() {
+    loom::model(|| {
+        let seq = Arc::new(AtomicTagPtr::null());
+        let data = Arc::new([AtomicUsize::new(0), AtomicUsize::new(0)]);
+
+        let (writer_seq, writer_data) = (Arc::clone(&seq), Arc::clone(&data));
+        let handle = thread::spawn(move || {
+            writer_seq.fetch_add(1, Ordering::Relaxed);
+            fence(Ordering::Release);
+            writer_data[0].store(1, Ordering::Relaxed);
+            writer_data[1].store(1, Ordering::Relaxed);
+            writer_seq.fetch_add(1, Ordering::Release);
+        });
+
+        let guard = seq.seq_guard();
+        if !guard.is_writing() {
+            let values = (data[0].load(Ordering::Relaxed), data[1].load(Ordering::Relaxed));
+            if guard.validate() {
+                assert_eq!(values.0, values.1);
+            }
+        }
+
+        handle.join().unwrap();
+    });
+}
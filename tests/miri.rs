@@ -0,0 +1,174 @@
+//! Exercises all unsafe entry points of the crate's API with real (stack or
+//! heap) allocations.
+//!
+//! These tests are meant to be run with `cargo +nightly miri test` in order to
+//! validate the absence of undefined behaviour, both with the default settings
+//! and with `MIRIFLAGS="-Zmiri-strict-provenance"`.
+//! The `from_usize` constructors are integer-to-pointer casts by definition and
+//! are hence not exercised here.
+
+use core::{
+    ptr::{self, NonNull},
+    sync::atomic::Ordering,
+};
+
+type AtomicTagPtr = tagptr::AtomicTagPtr<u64, 3>;
+type TagNonNull = tagptr::TagNonNull<u64, 3>;
+type TagPtr = tagptr::TagPtr<u64, 3>;
+
+const RELAXED: (Ordering, Ordering) = (Ordering::Relaxed, Ordering::Relaxed);
+
+#[test]
+fn tag_ptr_as_ref_and_as_mut() {
+    let mut value = 1u64;
+    let ptr = TagPtr::compose(&mut value, 0b101);
+
+    unsafe {
+        assert_eq!(ptr.as_ref(), Some(&1));
+        *ptr.as_mut().unwrap() += 1;
+        assert_eq!(ptr.decompose_ref(), (Some(&2), 0b101));
+
+        let (value, tag) = ptr.set_tag(0b010).decompose_mut();
+        *value.unwrap() += tag as u64;
+        assert_eq!(*ptr.clear_tag().as_ref().unwrap(), 4);
+
+        assert_eq!(TagPtr::compose(ptr::null_mut(), 0b11).as_ref(), None);
+        assert_eq!(TagPtr::compose(ptr::null_mut(), 0b11).decompose_mut(), (None, 0b11));
+    }
+
+    assert_eq!(value, 4);
+}
+
+#[test]
+fn tag_ptr_tag_arithmetic() {
+    let mut value = 1u64;
+    let ptr = TagPtr::new(&mut value).add_tag(0b110).sub_tag(0b010);
+
+    unsafe {
+        assert_eq!(ptr.decompose_ref(), (Some(&1), 0b100));
+        *ptr.update_tag(|tag| tag + 1).as_mut().unwrap() = 2;
+    }
+
+    assert_eq!(value, 2);
+}
+
+#[test]
+fn tag_ptr_fn_ptr() {
+    fn callback() {}
+
+    // function pointers are not guaranteed to be aligned under Miri
+    let ptr = tagptr::TagPtr::<(), 0>::from_fn_ptr(callback, 0);
+    let func = unsafe { ptr.as_fn_ptr() };
+    func();
+}
+
+#[test]
+fn tag_non_null_as_ref_and_as_mut() {
+    let mut value = 1u64;
+    let mut ptr = TagNonNull::compose(NonNull::from(&mut value), 0b011);
+
+    unsafe {
+        assert_eq!(ptr.as_ref(), &1);
+        *ptr.as_mut() += 1;
+        assert_eq!(ptr.decompose_ref(), (&2, 0b011));
+
+        let (value, tag) = ptr.decompose_mut();
+        *value += tag as u64;
+    }
+
+    assert_eq!(value, 5);
+}
+
+#[test]
+fn tag_non_null_unchecked() {
+    let mut value = 1u64;
+
+    unsafe {
+        let ptr = TagNonNull::new_unchecked(TagPtr::compose(&mut value, 0b1));
+        let ptr = TagNonNull::compose_unchecked(ptr.decompose_non_null(), 0b10);
+        assert_eq!(ptr.decompose_ref(), (&1, 0b10));
+
+        let mut ptr = ptr.add_tag(0b100).sub_tag(0b10);
+        *ptr.as_mut() = 2;
+        assert_eq!(ptr.decompose_ref(), (&2, 0b100));
+
+        let mut ptr = ptr.cast::<u32>().cast::<u64>();
+        *ptr.as_mut() = 3;
+    }
+
+    assert_eq!(value, 3);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn tag_non_null_box() {
+    let ptr = TagNonNull::from_box(Box::new(1), 0b111);
+    assert_eq!(unsafe { ptr.decompose_ref() }, (&1, 0b111));
+    assert_eq!(*unsafe { ptr.into_box() }, 1);
+}
+
+#[test]
+fn atomic_compare_exchange() {
+    let (mut a, mut b) = (1u64, 2u64);
+    let (a, b): (*mut u64, *mut u64) = (&mut a, &mut b);
+    let ptr = AtomicTagPtr::new(TagPtr::compose(a, 0b1));
+
+    let res = ptr.compare_exchange(TagPtr::compose(a, 0b1), TagPtr::new(b), RELAXED);
+    assert_eq!(res, Ok(TagPtr::compose(a, 0b1)));
+    let res = ptr.compare_exchange(TagPtr::compose(a, 0b1), TagPtr::new(b), RELAXED);
+    assert_eq!(res, Err(TagPtr::new(b)));
+
+    while ptr.compare_exchange_weak(TagPtr::new(b), TagPtr::compose(a, 0b11), RELAXED).is_err() {}
+    let res = ptr.compare_exchange_decompose(TagPtr::compose(a, 0b11), TagPtr::null(), RELAXED);
+    assert_eq!(res, Ok((a, 0b11)));
+
+    ptr.store(TagPtr::compose(b, 0b110), Ordering::Relaxed);
+    assert_eq!(ptr.compare_exchange_any_tag(b, TagPtr::new(a), RELAXED), Ok(0b110));
+    assert_eq!(ptr.load_ptr(Ordering::Relaxed), a);
+
+    unsafe {
+        *ptr.load(Ordering::Acquire).as_mut().unwrap() = 3;
+        assert_eq!(*a, 3);
+    }
+}
+
+#[test]
+fn atomic_fetch_update() {
+    let mut array = [1u64, 2, 3];
+    let base = array.as_mut_ptr();
+    let ptr = AtomicTagPtr::new(TagPtr::compose(base, 0b1));
+
+    let (res, _) = ptr.fetch_update_counted(RELAXED, |ptr| Some(ptr.add_tag(1)));
+    assert_eq!(res, Ok(TagPtr::compose(base, 0b1)));
+    let res = ptr.fetch_update_ptr(RELAXED, |ptr| Some(ptr.wrapping_add(2)));
+    assert_eq!(res, Ok(base));
+
+    unsafe {
+        assert_eq!(ptr.load(Ordering::Relaxed).decompose_ref(), (Some(&3), 0b10));
+        *ptr.load_non_null(Ordering::Relaxed).unwrap().as_mut() = 4;
+    }
+
+    let (last, tag) = ptr.take_decompose(Ordering::Relaxed);
+    assert_eq!(tag, 0b10);
+    assert_eq!(unsafe { *last }, 4);
+    assert_eq!(array, [1, 2, 4]);
+}
+
+#[test]
+fn atomic_swap_and_fetch_ops() {
+    let mut value = 1u64;
+    let ptr = AtomicTagPtr::null();
+
+    assert!(ptr.swap(TagPtr::new(&mut value), Ordering::Relaxed).is_null());
+    assert_eq!(ptr.fetch_or(0b101, Ordering::Relaxed).decompose_tag(), 0);
+    assert_eq!(ptr.fetch_and(0b100, Ordering::Relaxed).decompose_tag(), 0b101);
+    assert_eq!(ptr.fetch_add(0b1, Ordering::Relaxed).decompose_tag(), 0b100);
+    assert_eq!(ptr.fetch_sub(0b1, Ordering::Relaxed).decompose_tag(), 0b101);
+
+    unsafe {
+        let (value, tag) = ptr.load(Ordering::Relaxed).decompose_mut();
+        *value.unwrap() += tag as u64;
+    }
+
+    assert_eq!(value, 5);
+}
@@ -0,0 +1,48 @@
+//! Verifies that the operations guarded by debug assertions do not panic (in
+//! any build) with the `no-panic` feature enabled.
+
+#![cfg(feature = "no-panic")]
+
+use core::{ptr, sync::atomic::Ordering};
+
+type AtomicTagPtr = tagptr::AtomicTagPtr<u64, 2>;
+type TagPtr = tagptr::TagPtr<u64, 2>;
+
+const RELAXED: (Ordering, Ordering) = (Ordering::Relaxed, Ordering::Relaxed);
+
+#[test]
+fn compose_misaligned() {
+    let misaligned = ptr::without_provenance_mut(0x1001);
+    assert_eq!(TagPtr::compose(misaligned, 0b10).into_usize(), 0x1003);
+}
+
+#[test]
+fn tag_bit_out_of_range() {
+    let ptr = TagPtr::compose(ptr::null_mut(), 0b01);
+    assert!(ptr.tag_differs_only_in(ptr, 5));
+}
+
+#[test]
+fn non_null_with_bits() {
+    let ptr = tagptr::TagNonNull::<u64, 2>::try_from_usize(0b100).unwrap();
+    assert_eq!(ptr.with_bits::<3>().decompose_tag(), 0b100);
+}
+
+#[test]
+fn atomic_invalid_orderings() {
+    let ptr = AtomicTagPtr::null();
+    let res =
+        ptr.compare_exchange(TagPtr::null(), TagPtr::null(), (Ordering::Relaxed, Ordering::SeqCst));
+    assert_eq!(res, Ok(TagPtr::null()));
+}
+
+#[test]
+fn atomic_checked_values() {
+    let ptr = AtomicTagPtr::new(TagPtr::compose(ptr::without_provenance_mut(0x1000), 0));
+    assert!(!ptr.fetch_dec_refcount_tag(Ordering::Relaxed).1);
+    assert_eq!(ptr.load(Ordering::Relaxed).decompose(), (ptr::without_provenance_mut(0x1000), 0));
+
+    let ptr = AtomicTagPtr::null();
+    let res = ptr.fetch_update_ptr(RELAXED, |ptr| Some(ptr.wrapping_byte_add(1)));
+    assert_eq!(res, Ok(ptr::null_mut()));
+}